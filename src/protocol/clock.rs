@@ -0,0 +1,36 @@
+use embedded_hal::i2c::SevenBitAddress;
+
+use super::super::registers::hardware_configuration::device_config2::DeviceConfig2;
+use super::super::registers::hardware_configuration::peri_config1::PeriConfig1;
+use super::super::registers::settings::clock::{ClockConfig, ClockError};
+use super::device::{MCF8316C, ReadError};
+use super::interlock::InterlockError;
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    /// Switches the device to the given clock configuration.
+    ///
+    /// `DeviceConfig2` is written in the order given by
+    /// [`ClockConfig::device_config2_sequence`], then the spread spectrum setting is written.
+    /// Every write goes through [`MCF8316C::write_checked`], so the motor has to be idle.
+    pub fn set_clock(&mut self, config: &ClockConfig) -> Result<(), InterlockError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        for step in config.device_config2_sequence(device_config2) {
+            if step != device_config2 {
+                self.write_checked(&step)?;
+            }
+        }
+        let mut peri_config1: PeriConfig1 = self.read()?;
+        config.apply_spread_spectrum(&mut peri_config1);
+        self.write_checked(&peri_config1)
+    }
+
+    /// Reads back the active clock configuration.
+    ///
+    /// The outer result holds bus errors, the inner one registers that do not describe a valid
+    /// clock configuration.
+    pub fn clock(&mut self) -> Result<Result<ClockConfig, ClockError>, ReadError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        let peri_config1: PeriConfig1 = self.read()?;
+        Ok(ClockConfig::from_registers(&device_config2, &peri_config1))
+    }
+}
@@ -0,0 +1,58 @@
+use embedded_hal::i2c::SevenBitAddress;
+
+use super::super::registers::algorithm_control::dac_1::Dac1;
+use super::super::registers::algorithm_control::dac_2::Dac2;
+use super::super::registers::algorithm_control::dac_routing::{DacChannel, DacRoute};
+use super::super::registers::hardware_configuration::device_config1::{DacSoxSel, DeviceConfig1};
+use super::device::{MCF8316C, ReadError};
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    /// Drives the routed variable onto a DAC pin and enables the DAC outputs.
+    ///
+    /// Routing to DACOUT2 also selects DACOUT2 over the SOx outputs in `DeviceConfig1`.
+    ///
+    /// The DAC is a debug aid used while the motor runs, so `DeviceConfig1` is written without
+    /// the safe-write interlock. Only `dac_enable` and `dac_sox_sel` are modified.
+    pub fn route_dac(
+        &mut self,
+        channel: DacChannel,
+        route: &DacRoute,
+    ) -> Result<(), ReadError<I2C::Error>> {
+        match channel {
+            DacChannel::DacOut1 => {
+                let mut dac1: Dac1 = self.read()?;
+                route.apply_dac1(&mut dac1);
+                self.write(&dac1)?;
+            }
+            DacChannel::DacOut2 => {
+                let mut dac2: Dac2 = self.read()?;
+                route.apply_dac2(&mut dac2);
+                self.write(&dac2)?;
+            }
+        }
+        let mut device_config1: DeviceConfig1 = self.read()?;
+        device_config1.set_dac_enable(true);
+        if channel == DacChannel::DacOut2 {
+            device_config1.set_dac_sox_sel(DacSoxSel::DacOut2);
+        }
+        self.write(&device_config1)?;
+        Ok(())
+    }
+
+    /// Reads back the variable routed to a DAC pin.
+    pub fn dac_route(&mut self, channel: DacChannel) -> Result<DacRoute, ReadError<I2C::Error>> {
+        Ok(match channel {
+            DacChannel::DacOut1 => DacRoute::from_dac1(&self.read()?),
+            DacChannel::DacOut2 => DacRoute::from_dac2(&self.read()?),
+        })
+    }
+
+    /// Disables the DAC outputs on the dedicated DAC pins.
+    ///
+    /// Like [`route_dac`](Self::route_dac), this bypasses the safe-write interlock.
+    pub fn disable_dac(&mut self) -> Result<(), ReadError<I2C::Error>> {
+        let device_config1: DeviceConfig1 = self.read()?;
+        self.write(&device_config1.with_dac_enable(false))?;
+        Ok(())
+    }
+}
@@ -119,6 +119,9 @@ impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
     }
 
     /// Writes data to the specified register.
+    ///
+    /// This does not check the motor state. Use [`write_checked`](Self::write_checked) for
+    /// registers that must not be written while the motor is running.
     pub fn write<T: Register>(&mut self, data: &T) -> Result<(), I2C::Error> {
         self.write_u32(T::ADDRESS, data.value())
     }
@@ -0,0 +1,55 @@
+use arbitrary_int::u12;
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::Register;
+use super::super::registers::algorithm_variables::algorithm_state::MotorState;
+use super::device::{MCF8316C, ReadError};
+
+/// Error type for writes guarded by the safe-write interlock.
+#[derive(Error, Debug)]
+pub enum InterlockError<T> {
+    /// I2C communication error.
+    #[error("bus error: {0}")]
+    Bus(#[from] ReadError<T>),
+    /// The register must not be written unless the motor is idle.
+    #[error("register {address} must not be written in state {state:?}")]
+    MotorNotIdle {
+        /// Address of the refused register
+        address: u12,
+        /// Algorithm state read, or `None` for an undocumented value
+        state: Option<MotorState>,
+    },
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    /// Checks whether `T` may be written now.
+    ///
+    /// Registers with [`Register::SAFE_WHILE_RUNNING`] always pass without touching the bus.
+    /// Any other register requires ALGORITHM_STATE to read back idle.
+    pub fn check_write<T: Register>(&mut self) -> Result<(), InterlockError<I2C::Error>> {
+        if T::SAFE_WHILE_RUNNING {
+            return Ok(());
+        }
+        match self.algorithm_state()? {
+            Some(MotorState::Idle) => Ok(()),
+            state => Err(InterlockError::MotorNotIdle {
+                address: T::ADDRESS,
+                state,
+            }),
+        }
+    }
+
+    /// Writes a register after [`check_write`](Self::check_write) allows it.
+    ///
+    /// The check and the write are two separate transactions, so a motor started from the
+    /// SPEED pin in between is not caught.
+    pub fn write_checked<T: Register>(
+        &mut self,
+        data: &T,
+    ) -> Result<(), InterlockError<I2C::Error>> {
+        self.check_write::<T>()?;
+        self.write(data)
+            .map_err(|e| InterlockError::Bus(ReadError::from(e)))
+    }
+}
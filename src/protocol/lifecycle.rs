@@ -0,0 +1,224 @@
+use arbitrary_int::{u12, u15};
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::Register;
+use super::super::registers::common::Quantized;
+use super::device::{MCF8316C, ReadError};
+use super::interlock::InterlockError;
+use super::motion::DirectionChangeMode;
+use super::motor::{Motor, MotorError, MotorFaults, MotorStatus};
+
+/// Configuration has not been written and verified yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unverified;
+
+/// Configuration has been written and verified, motor not commanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configured;
+
+/// Motor commanded over I2C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Running;
+
+/// Error type for the [`Lifecycle`] typestate layer.
+#[derive(Error, Debug)]
+pub enum LifecycleError<T> {
+    /// I2C communication error.
+    #[error("bus error: {0}")]
+    Bus(#[from] ReadError<T>),
+    /// Error from the motor façade.
+    #[error(transparent)]
+    Motor(#[from] MotorError<T>),
+    /// A register did not read back the value written, outside of `Register::VERIFY_MASK`.
+    #[error("register {address} read back {actual:#010X} instead of {expected:#010X}")]
+    VerifyFailed {
+        /// Address of the register
+        address: u12,
+        /// Value written
+        expected: u32,
+        /// Value read back
+        actual: u32,
+    },
+    /// Write refused by the safe-write interlock.
+    #[error(transparent)]
+    Interlock(#[from] InterlockError<T>),
+}
+
+/// Result of a consuming transition. On failure the driver is handed back in state `S`.
+pub type Transition<I2C, T, S> = Result<
+    T,
+    (
+        Lifecycle<I2C, S>,
+        LifecycleError<<I2C as embedded_hal::i2c::ErrorType>::Error>,
+    ),
+>;
+
+/// Typestate layer over [`MCF8316C`] tracking whether the configuration is loaded and the
+/// motor is running.
+///
+/// Transitions consume the driver. A failed transition hands the driver back in its previous
+/// state along with the error. While [`Running`], only registers with
+/// [`Register::SAFE_WHILE_RUNNING`] can be written; anything else fails to compile.
+#[derive(Debug)]
+pub struct Lifecycle<I2C: embedded_hal::i2c::I2c<SevenBitAddress>, S> {
+    motor: Motor<I2C>,
+    state: S,
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>, S> Lifecycle<I2C, S> {
+    /// Reads any register.
+    pub fn read<T: Register>(&mut self) -> Result<T, ReadError<I2C::Error>> {
+        self.motor.device().read()
+    }
+
+    /// Returns the current state marker.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Releases the underlying device, leaving the typestate layer.
+    pub fn release(self) -> MCF8316C<I2C> {
+        self.motor.release()
+    }
+
+    fn into_state<N>(self, state: N) -> Lifecycle<I2C, N> {
+        Lifecycle {
+            motor: self.motor,
+            state,
+        }
+    }
+
+    fn write_verified<T: Register>(
+        &mut self,
+        register: &T,
+    ) -> Result<(), LifecycleError<I2C::Error>> {
+        self.motor.device().write_checked(register)?;
+        self.verify_register(register)
+    }
+
+    fn verify_register<T: Register>(
+        &mut self,
+        expected: &T,
+    ) -> Result<(), LifecycleError<I2C::Error>> {
+        let expected = expected.value();
+        let actual = self.motor.device().read_u32(T::ADDRESS)?;
+        if (actual ^ expected) & T::VERIFY_MASK == 0 {
+            Ok(())
+        } else {
+            Err(LifecycleError::VerifyFailed {
+                address: T::ADDRESS,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> Lifecycle<I2C, Unverified> {
+    /// Wraps the given device. The configuration is considered unverified.
+    pub fn new(device: MCF8316C<I2C>) -> Self {
+        Lifecycle {
+            motor: Motor::new(device),
+            state: Unverified,
+        }
+    }
+
+    /// Writes a register through [`MCF8316C::write_checked`] and reads it back to verify it.
+    pub fn write<T: Register>(&mut self, register: &T) -> Result<(), LifecycleError<I2C::Error>> {
+        self.write_verified(register)
+    }
+
+    /// Checks that a register already holds the expected value, e.g. loaded from EEPROM.
+    pub fn verify<T: Register>(&mut self, expected: &T) -> Result<(), LifecycleError<I2C::Error>> {
+        self.verify_register(expected)
+    }
+
+    /// Runs `load` to write and verify the configuration through [`write`](Self::write) and
+    /// [`verify`](Self::verify).
+    pub fn configure(
+        mut self,
+        load: impl FnOnce(&mut Self) -> Result<(), LifecycleError<I2C::Error>>,
+    ) -> Transition<I2C, Lifecycle<I2C, Configured>, Unverified> {
+        match load(&mut self) {
+            Ok(()) => Ok(self.into_state(Configured)),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> Lifecycle<I2C, Configured> {
+    /// Writes a register through [`MCF8316C::write_checked`] and reads it back to verify it.
+    pub fn write<T: Register>(&mut self, register: &T) -> Result<(), LifecycleError<I2C::Error>> {
+        self.write_verified(register)
+    }
+
+    /// Goes back to the unverified state, e.g. to load a different configuration.
+    pub fn reconfigure(self) -> Lifecycle<I2C, Unverified> {
+        self.into_state(Unverified)
+    }
+
+    /// Starts the motor at the given speed in percent of the maximum speed.
+    /// See [`Motor::start`].
+    pub fn start(
+        mut self,
+        speed_percent: f32,
+    ) -> Transition<I2C, (Lifecycle<I2C, Running>, Quantized<u15>), Configured> {
+        match self.motor.start(speed_percent) {
+            Ok(speed) => Ok((self.into_state(Running), speed)),
+            Err(e) => Err((self, e.into())),
+        }
+    }
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> Lifecycle<I2C, Running> {
+    /// Writes a register that is safe to write while the motor is running.
+    ///
+    /// Registers without [`Register::SAFE_WHILE_RUNNING`] are rejected at compile time.
+    pub fn write<T: Register>(&mut self, register: &T) -> Result<(), LifecycleError<I2C::Error>> {
+        const {
+            assert!(
+                T::SAFE_WHILE_RUNNING,
+                "register must not be written while the motor is running"
+            )
+        };
+        self.motor
+            .device()
+            .write(register)
+            .map_err(|e| LifecycleError::Bus(ReadError::from(e)))
+    }
+
+    /// Commands the given speed in percent of the maximum speed. See [`Motor::set_speed`].
+    pub fn set_speed(
+        &mut self,
+        speed_percent: f32,
+    ) -> Result<Quantized<u15>, LifecycleError<I2C::Error>> {
+        Ok(self.motor.set_speed(speed_percent)?)
+    }
+
+    /// Reverses the direction forced over I2C. See [`Motor::reverse`].
+    pub fn reverse(&mut self) -> Result<DirectionChangeMode, LifecycleError<I2C::Error>> {
+        Ok(self.motor.reverse()?)
+    }
+
+    /// Reads the algorithm state, direction and speed command.
+    pub fn status(&mut self) -> Result<MotorStatus, LifecycleError<I2C::Error>> {
+        Ok(self.motor.status()?)
+    }
+
+    /// Reads the gate driver and controller fault status.
+    pub fn faults(&mut self) -> Result<MotorFaults, LifecycleError<I2C::Error>> {
+        Ok(self.motor.faults()?)
+    }
+
+    /// Commands zero speed. The motor stops according to the configured stop policy.
+    ///
+    /// The motor may still be spinning down; writes in the configured state go through the
+    /// safe-write interlock.
+    pub fn stop(mut self) -> Transition<I2C, Lifecycle<I2C, Configured>, Running> {
+        match self.motor.set_speed(0.0) {
+            Ok(_) => Ok(self.into_state(Configured)),
+            Err(e) => Err((self, e.into())),
+        }
+    }
+}
@@ -5,8 +5,22 @@
 //! Section 7.6
 //! 
 
+mod clock;
 mod control_word;
+mod dac;
 mod device;
+mod interlock;
+mod lifecycle;
+mod motion;
+mod motor;
+mod power;
+mod watchdog;
 
 pub use control_word::*;
 pub use device::*;
+pub use interlock::*;
+pub use lifecycle::*;
+pub use motion::*;
+pub use motor::*;
+pub use power::*;
+pub use watchdog::*;
@@ -0,0 +1,253 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::algorithm_variables::algorithm_state::{AlgorithmState, MotorState};
+use super::super::registers::hardware_configuration::peri_config1::{
+    DirectionPinOverride, PeriConfig1,
+};
+use super::super::registers::hardware_configuration::pin_config::{BrakeInput, PinConfig};
+use super::device::{MCF8316C, ReadError};
+
+/// Interval between two reads of ALGORITHM_STATE while waiting for a confirmation.
+const POLL_INTERVAL_MS: u32 = 5;
+
+/// Direction of rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum Direction {
+    /// Clockwise rotation OUTA-OUTB-OUTC
+    #[strum(to_string = "Clockwise")]
+    Clockwise,
+    /// Counter-clockwise rotation OUTA-OUTC-OUTB
+    #[strum(to_string = "Counter-clockwise")]
+    CounterClockwise,
+}
+
+impl Direction {
+    /// Returns the DIR pin override selecting this direction.
+    pub fn pin_override(&self) -> DirectionPinOverride {
+        match self {
+            Direction::Clockwise => DirectionPinOverride::OverrideClockwise,
+            Direction::CounterClockwise => DirectionPinOverride::OverrideCounterClockwise,
+        }
+    }
+
+    /// Returns the direction forced by a DIR pin override, or `None` if the pin is followed.
+    pub fn from_pin_override(dir_input: DirectionPinOverride) -> Option<Self> {
+        match dir_input {
+            DirectionPinOverride::OverrideClockwise => Some(Direction::Clockwise),
+            DirectionPinOverride::OverrideCounterClockwise => Some(Direction::CounterClockwise),
+            DirectionPinOverride::Hardware | DirectionPinOverride::Hardware2 => None,
+        }
+    }
+}
+
+/// How the device reacts to a direction change, from `DIR_CHANGE_MODE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum DirectionChangeMode {
+    /// Stop the motor following the stop options, then restart through ISD
+    #[strum(to_string = "Stop first")]
+    StopFirst,
+    /// Reverse through reverse drive while continuously driving the motor
+    #[strum(to_string = "On the fly")]
+    OnTheFly,
+}
+
+impl DirectionChangeMode {
+    /// Returns the mode selected by the `dir_change_mode` bit.
+    pub fn from_bit(dir_change_mode: bool) -> Self {
+        if dir_change_mode {
+            DirectionChangeMode::OnTheFly
+        } else {
+            DirectionChangeMode::StopFirst
+        }
+    }
+}
+
+/// Error type for direction and brake changes confirmed through ALGORITHM_STATE.
+#[derive(Error, Debug)]
+pub enum MotionError<T> {
+    /// I2C communication error.
+    #[error("bus error: {0}")]
+    Bus(#[from] ReadError<T>),
+    /// The expected state was not reached in time
+    #[error("state not confirmed in time, last state: {last:?}")]
+    Timeout {
+        /// Last state read before giving up
+        last: Option<MotorState>,
+    },
+    /// The device entered the fault state while waiting
+    #[error("device entered the fault state")]
+    Fault,
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    /// Reads the current algorithm state, or `None` for an undocumented value.
+    pub fn algorithm_state(&mut self) -> Result<Option<MotorState>, ReadError<I2C::Error>> {
+        let state: AlgorithmState = self.read()?;
+        Ok(state.algorithm_state())
+    }
+
+    /// Overrides the DIR pin with the given direction.
+    ///
+    /// Only writes PERI_CONFIG1 if the direction actually changes. Returns how the device
+    /// carries out the change, so the caller knows whether the motor will stop on the way.
+    ///
+    /// The DIR override is meant to change while the motor runs, so PERI_CONFIG1 is written
+    /// without the safe-write interlock. Only `dir_input` is modified.
+    pub fn set_direction(
+        &mut self,
+        direction: Direction,
+    ) -> Result<DirectionChangeMode, ReadError<I2C::Error>> {
+        Ok(self.write_direction(direction)?.0)
+    }
+
+    /// Hands direction control back to the DIR pin.
+    ///
+    /// Like [`set_direction`](Self::set_direction), this bypasses the safe-write interlock.
+    pub fn follow_direction_pin(&mut self) -> Result<(), ReadError<I2C::Error>> {
+        let peri_config1: PeriConfig1 = self.read()?;
+        self.write(&peri_config1.with_dir_input(DirectionPinOverride::Hardware))?;
+        Ok(())
+    }
+
+    /// Reads the direction forced over I2C, or `None` if the DIR pin is followed.
+    pub fn direction(&mut self) -> Result<Option<Direction>, ReadError<I2C::Error>> {
+        let peri_config1: PeriConfig1 = self.read()?;
+        Ok(Direction::from_pin_override(peri_config1.dir_input()))
+    }
+
+    /// Same as [`set_direction`](Self::set_direction), then waits for the device to carry out
+    /// the change.
+    ///
+    /// With [`DirectionChangeMode::StopFirst`] the motor has to go through a stop or ISD state
+    /// and come back to closed loop. With [`DirectionChangeMode::OnTheFly`] it has to leave
+    /// closed loop for reverse drive and come back. If the motor was idle before the change,
+    /// staying idle confirms it, since the new direction applies on the next start. Nothing is
+    /// awaited if the direction was unchanged.
+    pub fn set_direction_and_wait<D: DelayNs>(
+        &mut self,
+        direction: Direction,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<DirectionChangeMode, MotionError<I2C::Error>> {
+        let before = self.algorithm_state()?;
+        let (mode, changed) = self.write_direction(direction)?;
+        if !changed {
+            return Ok(mode);
+        }
+        let was_idle = before == Some(MotorState::Idle);
+        let mut stopped = false;
+        let mut seen_closed_loop = before.is_some_and(|state| state.is_closed_loop());
+        let mut left_closed_loop = false;
+        self.wait_for_state(delay, timeout_ms, |state| {
+            if state == MotorState::Idle {
+                return was_idle;
+            }
+            let driving = matches!(
+                state,
+                MotorState::ClosedLoopUnaligned | MotorState::ClosedLoopAligned
+            );
+            match mode {
+                DirectionChangeMode::StopFirst => {
+                    stopped |= state.is_stopping();
+                    stopped && driving
+                }
+                DirectionChangeMode::OnTheFly => {
+                    left_closed_loop |= seen_closed_loop && !state.is_closed_loop();
+                    seen_closed_loop |= state.is_closed_loop();
+                    left_closed_loop && driving
+                }
+            }
+        })?;
+        Ok(mode)
+    }
+
+    /// Overrides the BRAKE pin and brakes or aligns according to `BRAKE_PIN_MODE`.
+    pub fn engage_brake(&mut self) -> Result<(), ReadError<I2C::Error>> {
+        self.set_brake_input(BrakeInput::Override)
+    }
+
+    /// Overrides the BRAKE pin and releases the brake.
+    pub fn release_brake(&mut self) -> Result<(), ReadError<I2C::Error>> {
+        self.set_brake_input(BrakeInput::OverrideNoBrake)
+    }
+
+    /// Hands brake control back to the BRAKE pin.
+    pub fn follow_brake_pin(&mut self) -> Result<(), ReadError<I2C::Error>> {
+        self.set_brake_input(BrakeInput::Pin)
+    }
+
+    /// Same as [`engage_brake`](Self::engage_brake), then waits for the brake on stop or
+    /// idle state.
+    pub fn engage_brake_and_wait<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), MotionError<I2C::Error>> {
+        self.engage_brake()?;
+        self.wait_for_state(delay, timeout_ms, |state| {
+            matches!(state, MotorState::BrakeOnStop | MotorState::Idle)
+        })?;
+        Ok(())
+    }
+
+    /// Same as [`release_brake`](Self::release_brake), then waits for the device to leave
+    /// the brake on stop state.
+    pub fn release_brake_and_wait<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), MotionError<I2C::Error>> {
+        self.release_brake()?;
+        self.wait_for_state(delay, timeout_ms, |state| state != MotorState::BrakeOnStop)?;
+        Ok(())
+    }
+
+    /// Polls ALGORITHM_STATE until `confirmed` returns true, the device faults, or
+    /// `timeout_ms` elapses. Returns the confirming state.
+    pub fn wait_for_state<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+        mut confirmed: impl FnMut(MotorState) -> bool,
+    ) -> Result<MotorState, MotionError<I2C::Error>> {
+        let mut waited_ms = 0;
+        loop {
+            let last = self.algorithm_state()?;
+            match last {
+                Some(MotorState::Fault) => return Err(MotionError::Fault),
+                Some(state) if confirmed(state) => return Ok(state),
+                _ if waited_ms >= timeout_ms => return Err(MotionError::Timeout { last }),
+                _ => {
+                    delay.delay_ms(POLL_INTERVAL_MS);
+                    waited_ms += POLL_INTERVAL_MS;
+                }
+            }
+        }
+    }
+
+    /// Read-modify-write of the DIR override. Also returns whether the register changed.
+    fn write_direction(
+        &mut self,
+        direction: Direction,
+    ) -> Result<(DirectionChangeMode, bool), ReadError<I2C::Error>> {
+        let peri_config1: PeriConfig1 = self.read()?;
+        let changed = peri_config1.dir_input() != direction.pin_override();
+        if changed {
+            self.write(&peri_config1.with_dir_input(direction.pin_override()))?;
+        }
+        Ok((
+            DirectionChangeMode::from_bit(peri_config1.dir_change_mode()),
+            changed,
+        ))
+    }
+
+    fn set_brake_input(&mut self, brake_input: BrakeInput) -> Result<(), ReadError<I2C::Error>> {
+        let pin_config: PinConfig = self.read()?;
+        if pin_config.brake_input() != brake_input {
+            self.write(&pin_config.with_brake_input(brake_input))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use arbitrary_int::u15;
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::Register;
+use super::super::registers::algorithm_configuration::closed_loop2::ClosedLoop2;
+use super::super::registers::algorithm_configuration::motor_startup1::MotorStartup1;
+use super::super::registers::algorithm_configuration::rev_drive_config::RevDriveConfig;
+use super::super::registers::algorithm_control::algo_ctrl1::AlgoCtrl1;
+use super::super::registers::algorithm_control::algo_debug1::AlgoDebug1;
+use super::super::registers::algorithm_variables::algorithm_state::MotorState;
+use super::super::registers::common::{OutOfRangeError, Quantized};
+use super::super::registers::fault_status::controller_fault_status::ControllerFaultStatus;
+use super::super::registers::fault_status::gate_driver_fault_status::GateDriverFaultStatus;
+use super::super::registers::hardware_configuration::peri_config1::PeriConfig1;
+use super::super::registers::hardware_configuration::pin_config::{PinConfig, SpeedMode};
+use super::super::registers::internal_algorithm_configuration::int_algo2::IntAlgo2;
+use super::super::registers::settings::stop_policy::StopPolicy;
+use super::device::{MCF8316C, ReadError};
+use super::interlock::InterlockError;
+use super::motion::{Direction, DirectionChangeMode};
+
+/// Snapshot of what the motor is doing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorStatus {
+    /// Current algorithm state, or `None` for an undocumented value
+    pub state: Option<MotorState>,
+    /// Direction forced over I2C, or `None` if the DIR pin is followed
+    pub direction: Option<Direction>,
+    /// Speed command in percent of the maximum speed
+    pub speed_percent: f32,
+}
+
+impl MotorStatus {
+    /// Returns true while the device is starting, driving or stopping the motor.
+    pub fn is_running(&self) -> bool {
+        self.state.is_some_and(|state| state.is_running())
+    }
+}
+
+/// Gate driver and controller fault status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotorFaults {
+    /// Gate driver fault status
+    pub gate_driver: GateDriverFaultStatus,
+    /// Controller fault status
+    pub controller: ControllerFaultStatus,
+}
+
+impl MotorFaults {
+    /// Returns true if any gate driver or controller fault is active.
+    pub fn any(&self) -> bool {
+        self.gate_driver.driver_fault() || self.controller.controller_fault()
+    }
+}
+
+/// Error type for the [`Motor`] façade.
+#[derive(Error, Debug)]
+pub enum MotorError<T> {
+    /// I2C communication error.
+    #[error("bus error: {0}")]
+    Bus(#[from] ReadError<T>),
+    /// Requested speed outside 0-100%.
+    #[error(transparent)]
+    OutOfRange(#[from] OutOfRangeError),
+    /// Configuration write refused by the safe-write interlock.
+    #[error(transparent)]
+    Interlock(#[from] InterlockError<T>),
+    /// The direction follows the DIR pin, so it cannot be reversed over I2C.
+    #[error("direction is controlled by the DIR pin")]
+    DirectionPinControlled,
+}
+
+/// High-level motor controller driving the speed over I2C.
+///
+/// Wraps an [`MCF8316C`] and hides which registers control the speed, direction, brake and
+/// stop behavior. The raw register API stays available through [`Motor::device`].
+#[derive(Debug)]
+pub struct Motor<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> {
+    device: MCF8316C<I2C>,
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> Motor<I2C> {
+    /// Wraps the given device. No register is touched until the motor is started.
+    pub fn new(device: MCF8316C<I2C>) -> Self {
+        Motor { device }
+    }
+
+    /// Returns the underlying device for raw register access.
+    pub fn device(&mut self) -> &mut MCF8316C<I2C> {
+        &mut self.device
+    }
+
+    /// Releases the underlying device.
+    pub fn release(self) -> MCF8316C<I2C> {
+        self.device
+    }
+
+    /// Switches the speed input to I2C, releases the brake and commands the given speed in
+    /// percent of the maximum speed. Returns the speed command actually configured.
+    pub fn start(&mut self, speed_percent: f32) -> Result<Quantized<u15>, MotorError<I2C::Error>> {
+        let pin_config: PinConfig = self.device.read()?;
+        if pin_config.speed_mode() != SpeedMode::I2C {
+            self.write(&pin_config.with_speed_mode(SpeedMode::I2C))?;
+        }
+        self.device.release_brake()?;
+        self.set_speed(speed_percent)
+    }
+
+    /// Commands the given speed in percent of the maximum speed.
+    /// Returns the speed command actually configured.
+    pub fn set_speed(
+        &mut self,
+        speed_percent: f32,
+    ) -> Result<Quantized<u15>, MotorError<I2C::Error>> {
+        let mut algo_debug1: AlgoDebug1 = self.device.read()?;
+        let quantized = algo_debug1.set_digital_speed_percent(speed_percent)?;
+        algo_debug1.set_override_speed(true);
+        self.write(&algo_debug1)?;
+        Ok(quantized)
+    }
+
+    /// Commands zero speed. The motor stops as configured with
+    /// [`configure_stop`](Self::configure_stop).
+    pub fn stop(&mut self) -> Result<(), MotorError<I2C::Error>> {
+        self.set_speed(0.0)?;
+        Ok(())
+    }
+
+    /// Configures how the motor stops. Only registers the policy changes are written.
+    ///
+    /// If any changed register is not [`Register::SAFE_WHILE_RUNNING`], the motor has to be
+    /// idle. ALGORITHM_STATE is then checked once before anything is written, so a refused
+    /// policy leaves the device unchanged. A policy changing only safe registers is applied
+    /// while the motor runs.
+    pub fn configure_stop(&mut self, policy: &StopPolicy) -> Result<(), MotorError<I2C::Error>> {
+        let closed_loop2: ClosedLoop2 = self.device.read()?;
+        let pin_config: PinConfig = self.device.read()?;
+        let motor_startup1: MotorStartup1 = self.device.read()?;
+        let int_algo2: IntAlgo2 = self.device.read()?;
+        let rev_drive_config: RevDriveConfig = self.device.read()?;
+        let peri_config1: PeriConfig1 = self.device.read()?;
+        let mut new_closed_loop2 = closed_loop2;
+        let mut new_pin_config = pin_config;
+        let mut new_motor_startup1 = motor_startup1;
+        let mut new_int_algo2 = int_algo2;
+        let mut new_rev_drive_config = rev_drive_config;
+        let mut new_peri_config1 = peri_config1;
+        policy.apply(
+            &mut new_closed_loop2,
+            &mut new_pin_config,
+            &mut new_motor_startup1,
+            &mut new_int_algo2,
+            &mut new_rev_drive_config,
+            &mut new_peri_config1,
+        );
+        let mut idle_checked = false;
+        self.check_if_changed(&closed_loop2, &new_closed_loop2, &mut idle_checked)?;
+        self.check_if_changed(&pin_config, &new_pin_config, &mut idle_checked)?;
+        self.check_if_changed(&motor_startup1, &new_motor_startup1, &mut idle_checked)?;
+        self.check_if_changed(&int_algo2, &new_int_algo2, &mut idle_checked)?;
+        self.check_if_changed(&rev_drive_config, &new_rev_drive_config, &mut idle_checked)?;
+        self.check_if_changed(&peri_config1, &new_peri_config1, &mut idle_checked)?;
+        self.write_if_changed(&closed_loop2, &new_closed_loop2)?;
+        self.write_if_changed(&pin_config, &new_pin_config)?;
+        self.write_if_changed(&motor_startup1, &new_motor_startup1)?;
+        self.write_if_changed(&int_algo2, &new_int_algo2)?;
+        self.write_if_changed(&rev_drive_config, &new_rev_drive_config)?;
+        self.write_if_changed(&peri_config1, &new_peri_config1)?;
+        Ok(())
+    }
+
+    /// Reverses the direction forced over I2C.
+    ///
+    /// Fails if the direction follows the DIR pin; use [`MCF8316C::set_direction`] to take
+    /// over the direction first.
+    pub fn reverse(&mut self) -> Result<DirectionChangeMode, MotorError<I2C::Error>> {
+        let direction = match self.device.direction()? {
+            Some(Direction::Clockwise) => Direction::CounterClockwise,
+            Some(Direction::CounterClockwise) => Direction::Clockwise,
+            None => return Err(MotorError::DirectionPinControlled),
+        };
+        Ok(self.device.set_direction(direction)?)
+    }
+
+    /// Reads the algorithm state, direction and speed command.
+    pub fn status(&mut self) -> Result<MotorStatus, MotorError<I2C::Error>> {
+        let algo_debug1: AlgoDebug1 = self.device.read()?;
+        Ok(MotorStatus {
+            state: self.device.algorithm_state()?,
+            direction: self.device.direction()?,
+            speed_percent: algo_debug1.digital_speed_percent(),
+        })
+    }
+
+    /// Reads the gate driver and controller fault status.
+    pub fn faults(&mut self) -> Result<MotorFaults, MotorError<I2C::Error>> {
+        Ok(MotorFaults {
+            gate_driver: self.device.read()?,
+            controller: self.device.read()?,
+        })
+    }
+
+    /// Clears latched faults.
+    pub fn clear_faults(&mut self) -> Result<(), MotorError<I2C::Error>> {
+        let algo_ctrl1: AlgoCtrl1 = self.device.read()?;
+        self.write(&AlgoCtrl1::without_actions(algo_ctrl1).with_clr_flt(true))
+    }
+
+    /// Runs the interlock check for `T` if it changes and no register was checked yet.
+    fn check_if_changed<T: Register + PartialEq>(
+        &mut self,
+        current: &T,
+        new: &T,
+        idle_checked: &mut bool,
+    ) -> Result<(), MotorError<I2C::Error>> {
+        if current != new && !T::SAFE_WHILE_RUNNING && !*idle_checked {
+            self.device.check_write::<T>()?;
+            *idle_checked = true;
+        }
+        Ok(())
+    }
+
+    /// Writes `new` if it differs from `current`. The caller runs the interlock check first.
+    fn write_if_changed<T: Register + PartialEq>(
+        &mut self,
+        current: &T,
+        new: &T,
+    ) -> Result<(), MotorError<I2C::Error>> {
+        if current != new {
+            self.device
+                .write(new)
+                .map_err(|e| MotorError::Bus(ReadError::from(e)))?;
+        }
+        Ok(())
+    }
+
+    fn write<T: Register>(&mut self, register: &T) -> Result<(), MotorError<I2C::Error>> {
+        Ok(self.device.write_checked(register)?)
+    }
+}
@@ -0,0 +1,103 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::hardware_configuration::device_config2::{
+    DeviceConfig2, LowPowerMode, SleepEntryTime,
+};
+use super::device::{MCF8316C, ReadError};
+use super::interlock::InterlockError;
+
+/// Retry schedule used by [`MCF8316C::wait_for_wake`].
+///
+/// The delay before each retry starts at `initial_delay_us` and doubles up to `max_delay_us`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WakeRetry {
+    /// Number of read attempts before giving up
+    pub attempts: u8,
+    /// Delay before the second attempt in microseconds
+    pub initial_delay_us: u32,
+    /// Longest delay between two attempts in microseconds
+    pub max_delay_us: u32,
+}
+
+impl Default for WakeRetry {
+    fn default() -> Self {
+        WakeRetry {
+            attempts: 10,
+            initial_delay_us: 500,
+            max_delay_us: 20_000,
+        }
+    }
+}
+
+/// Error type for waking the MCF8316C-Q1 device.
+#[derive(Error, Debug)]
+pub enum WakeError<T> {
+    /// The device did not answer with a valid CRC within the allowed attempts.
+    #[error("device did not answer after {attempts} attempts: {last}")]
+    NoResponse {
+        /// Number of attempts made
+        attempts: u8,
+        /// Error returned by the last attempt
+        last: ReadError<T>,
+    },
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    /// Configures the low power mode entered when the speed command goes to zero, and how long
+    /// the command must stay at zero before it is entered.
+    ///
+    /// `DeviceConfig2` is written through [`MCF8316C::write_checked`], so the motor has to be
+    /// idle.
+    pub fn configure_low_power(
+        &mut self,
+        mode: LowPowerMode,
+        entry_time: SleepEntryTime,
+    ) -> Result<(), InterlockError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        self.write_checked(
+            &device_config2
+                .with_low_power_mode(mode)
+                .with_sleep_entry_time(entry_time),
+        )
+    }
+
+    /// Reads back the configured low power mode and entry time.
+    pub fn low_power(&mut self) -> Result<(LowPowerMode, SleepEntryTime), ReadError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        Ok((
+            device_config2.low_power_mode(),
+            device_config2.sleep_entry_time(),
+        ))
+    }
+
+    /// Waits for the device to answer over I2C after waking, retrying with exponential backoff.
+    ///
+    /// Waking the device from sleep is done through the SPEED pin and is up to the caller;
+    /// this only re-establishes communication. Returns the number of attempts needed.
+    pub fn wait_for_wake<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        retry: WakeRetry,
+    ) -> Result<u8, WakeError<I2C::Error>> {
+        let mut delay_us = retry.initial_delay_us;
+        let mut attempt = 1;
+        loop {
+            match self.read::<DeviceConfig2>() {
+                Ok(_) => return Ok(attempt),
+                Err(last) if attempt >= retry.attempts => {
+                    return Err(WakeError::NoResponse {
+                        attempts: attempt,
+                        last,
+                    });
+                }
+                Err(_) => {
+                    delay.delay_us(delay_us);
+                    delay_us = delay_us.saturating_mul(2).min(retry.max_delay_us);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
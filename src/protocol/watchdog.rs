@@ -0,0 +1,176 @@
+use core::convert::Infallible;
+
+use embedded_hal::digital::{ErrorType, OutputPin};
+use embedded_hal::i2c::SevenBitAddress;
+use thiserror::Error;
+
+use super::super::registers::algorithm_control::algo_ctrl1::AlgoCtrl1;
+use super::super::registers::fault_status::controller_fault_status::ControllerFaultStatus;
+use super::super::registers::hardware_configuration::device_config2::{
+    DeviceConfig2, ExternalWatchdogConfig,
+};
+use super::device::{MCF8316C, ReadError};
+use super::interlock::InterlockError;
+
+/// Reaction of the device to a missed watchdog tickle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogFaultMode {
+    /// Report the fault only
+    ReportOnly,
+    /// Latch the fault with the FETs in Hi-Z
+    LatchHiZ,
+}
+
+/// Stand-in pin for a watchdog tickled over I2C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Error type for feeding and checking the external watchdog.
+#[derive(Error, Debug)]
+pub enum WatchdogError<E, P> {
+    /// I2C communication error.
+    #[error("bus error: {0}")]
+    Bus(#[from] ReadError<E>),
+    /// Configuration write refused by the safe-write interlock.
+    #[error(transparent)]
+    Interlock(#[from] InterlockError<E>),
+    /// Error from the GPIO pin used to tickle the watchdog.
+    #[error("pin error: {0:?}")]
+    Pin(P),
+    /// The device reported an external watchdog fault.
+    #[error("external watchdog fault")]
+    Fault,
+}
+
+/// Feeds the external watchdog enabled by [`MCF8316C::enable_i2c_watchdog`] or
+/// [`MCF8316C::enable_gpio_watchdog`].
+///
+/// Call [`WatchdogFeeder::feed`] more often than [`WatchdogFeeder::timeout_ms`].
+#[derive(Debug)]
+pub struct WatchdogFeeder<P: OutputPin> {
+    pin: Option<P>,
+    timeout: ExternalWatchdogConfig,
+}
+
+impl<P: OutputPin> WatchdogFeeder<P> {
+    /// Returns the longest allowed time between two feeds in milliseconds.
+    pub fn timeout_ms(&self) -> u32 {
+        self.timeout.timeout_ms(self.pin.is_none())
+    }
+
+    /// Tickles the watchdog.
+    ///
+    /// In GPIO mode the pin is driven high and back low, so every feed produces both a rising
+    /// and a falling edge. Over I2C, ALGO_CTRL1 is read back and written with only
+    /// `watchdog_tickle` set, see [`AlgoCtrl1::tickle_from`].
+    pub fn feed<I2C: embedded_hal::i2c::I2c<SevenBitAddress>>(
+        &mut self,
+        device: &mut MCF8316C<I2C>,
+    ) -> Result<(), WatchdogError<I2C::Error, P::Error>> {
+        match &mut self.pin {
+            Some(pin) => {
+                pin.set_high().map_err(WatchdogError::Pin)?;
+                pin.set_low().map_err(WatchdogError::Pin)
+            }
+            None => {
+                let current: AlgoCtrl1 = device.read()?;
+                device
+                    .write(&AlgoCtrl1::tickle_from(current))
+                    .map_err(|e| WatchdogError::Bus(ReadError::from(e)))
+            }
+        }
+    }
+
+    /// Reads the controller fault status and returns `WatchdogError::Fault` if the device
+    /// reports an external watchdog fault.
+    pub fn check<I2C: embedded_hal::i2c::I2c<SevenBitAddress>>(
+        &self,
+        device: &mut MCF8316C<I2C>,
+    ) -> Result<(), WatchdogError<I2C::Error, P::Error>> {
+        let status: ControllerFaultStatus = device.read()?;
+        if status.watchdog_fault() {
+            Err(WatchdogError::Fault)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Gives back the pin used to tickle the watchdog, if any.
+    pub fn release(self) -> Option<P> {
+        self.pin
+    }
+}
+
+impl<I2C: embedded_hal::i2c::I2c<SevenBitAddress>> MCF8316C<I2C> {
+    fn configure_external_watchdog(
+        &mut self,
+        timeout: ExternalWatchdogConfig,
+        fault_mode: WatchdogFaultMode,
+        over_gpio: bool,
+    ) -> Result<(), InterlockError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        let device_config2 = device_config2
+            .with_ext_wdt_en(true)
+            .with_ext_wdt_config(timeout)
+            .with_ext_wdt_input_mode(over_gpio)
+            .with_ext_wdt_fault_mode(fault_mode == WatchdogFaultMode::LatchHiZ);
+        self.write_checked(&device_config2)
+    }
+
+    /// Enables the external watchdog tickled over I2C.
+    /// The returned feeder must be fed at least once every `timeout`.
+    ///
+    /// `DeviceConfig2` is written through [`MCF8316C::write_checked`], so the motor has to be
+    /// idle.
+    pub fn enable_i2c_watchdog(
+        &mut self,
+        timeout: ExternalWatchdogConfig,
+        fault_mode: WatchdogFaultMode,
+    ) -> Result<WatchdogFeeder<NoPin>, InterlockError<I2C::Error>> {
+        self.configure_external_watchdog(timeout, fault_mode, false)?;
+        Ok(WatchdogFeeder { pin: None, timeout })
+    }
+
+    /// Enables the external watchdog tickled over GPIO, using `pin` to tickle it.
+    /// The pin is driven low first. The returned feeder must be fed at least once every
+    /// `timeout`.
+    ///
+    /// `DeviceConfig2` is written through [`MCF8316C::write_checked`], so the motor has to be
+    /// idle.
+    pub fn enable_gpio_watchdog<P: OutputPin>(
+        &mut self,
+        timeout: ExternalWatchdogConfig,
+        fault_mode: WatchdogFaultMode,
+        mut pin: P,
+    ) -> Result<WatchdogFeeder<P>, WatchdogError<I2C::Error, P::Error>> {
+        pin.set_low().map_err(WatchdogError::Pin)?;
+        self.configure_external_watchdog(timeout, fault_mode, true)?;
+        Ok(WatchdogFeeder {
+            pin: Some(pin),
+            timeout,
+        })
+    }
+
+    /// Disables the external watchdog.
+    ///
+    /// `DeviceConfig2` is written through [`MCF8316C::write_checked`], so the motor has to be
+    /// idle.
+    pub fn disable_external_watchdog(&mut self) -> Result<(), InterlockError<I2C::Error>> {
+        let device_config2: DeviceConfig2 = self.read()?;
+        self.write_checked(&device_config2.with_ext_wdt_en(false))
+    }
+}
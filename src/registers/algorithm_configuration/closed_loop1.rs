@@ -3,6 +3,7 @@
 use super::*;
 use arbitrary_int::*;
 use bitbybit::*;
+use core::num::NonZeroU8;
 
 /// Register to configure close loop settings1
 #[bitfield(u32, debug, default = 0x0)]
@@ -175,6 +176,19 @@ pub enum ClosedLoopAcceleration {
     NoLimit = 0x1F,
 }
 
+impl ClosedLoopAcceleration {
+    const HZ_PER_SECOND: [f32; 31] = [
+        0.5, 1.0, 2.5, 5.0, 7.5, 10.0, 20.0, 40.0, 60.0, 80.0, 100.0, 200.0, 300.0, 400.0, 500.0,
+        600.0, 700.0, 800.0, 900.0, 1000.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0, 20000.0,
+        30000.0, 40000.0, 50000.0, 60000.0, 70000.0,
+    ];
+
+    /// Returns the acceleration in Hz/s, or `None` if there is no limit.
+    pub fn to_hz_per_second(&self) -> Option<f32> {
+        Self::HZ_PER_SECOND.get(*self as usize).copied()
+    }
+}
+
 /// PWM output frequency.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
 #[bitenum(u4, exhaustive = false)]
@@ -285,6 +299,47 @@ pub enum FgDiv {
     Div15 = 0xF,
 }
 
+impl FgDiv {
+    /// Returns the division factor.
+    pub fn divisor(&self) -> u8 {
+        match self {
+            FgDiv::Div1 | FgDiv::TheCoolerDiv1 => 1,
+            other => *other as u8,
+        }
+    }
+
+    /// Returns the division factor that makes the FG frequency equal the mechanical speed of a
+    /// motor with the given number of pole pairs, or `None` if no factor matches.
+    pub fn for_pole_pairs(pole_pairs: u8) -> Option<Self> {
+        match pole_pairs {
+            1..=15 => Some(FgDiv::new_with_raw_value(u4::new(pole_pairs))),
+            _ => None,
+        }
+    }
+
+    /// Returns the FG frequency in Hz for an electrical speed in Hz.
+    pub fn fg_hz(&self, electrical_hz: f32) -> f32 {
+        electrical_hz / self.divisor() as f32
+    }
+
+    /// Returns the electrical speed in Hz for an FG frequency in Hz.
+    pub fn electrical_hz(&self, fg_hz: f32) -> f32 {
+        fg_hz * self.divisor() as f32
+    }
+
+    /// Returns the mechanical speed in RPM for an FG frequency in Hz, for a motor with the given
+    /// number of pole pairs.
+    pub fn mechanical_rpm(&self, fg_hz: f32, pole_pairs: NonZeroU8) -> f32 {
+        self.electrical_hz(fg_hz) * 60.0 / pole_pairs.get() as f32
+    }
+
+    /// Returns the FG frequency in Hz for a mechanical speed in RPM, for a motor with the given
+    /// number of pole pairs.
+    pub fn fg_hz_from_rpm(&self, rpm: f32, pole_pairs: NonZeroU8) -> f32 {
+        self.fg_hz(rpm * pole_pairs.get() as f32 / 60.0)
+    }
+}
+
 /// FG output BEMF threshold
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
 #[bitenum(u3, exhaustive = false)]
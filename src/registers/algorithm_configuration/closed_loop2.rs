@@ -68,7 +68,7 @@ pub enum MotorStop {
 
 /// Brake time during motor stop
 #[bitenum(u4, exhaustive = true)]
-#[derive(Debug, strum::Display)]
+#[derive(Debug, PartialEq, Eq, strum::Display)]
 pub enum MotorStopBrakeTime {
     // Why is 1ms listed 5 different ways? Nobody knows.
     /// 1 ms
@@ -1800,3 +1800,91 @@ impl From<u8> for MotorInductance {
         unsafe { core::mem::transmute(value) }
     }
 }
+
+/// Piecewise-linear layout of Tables 7-2 and 7-3, shared by the resistance and inductance codes.
+/// Each entry is (first code, value at first code, step per code).
+/// Resistance values are in Ω, inductance values are in mH.
+/// Table 7-4 (BEMF constant) uses the same layout scaled by 100, in mV/Hz.
+const MOTOR_PARAM_SEGMENTS: [(u8, f32, f32); 10] = [
+    (0x01, 0.006, 0.001),
+    (0x0F, 0.020, 0.002),
+    (0x37, 0.100, 0.005),
+    (0x87, 0.500, 0.010),
+    (0x9B, 0.700, 0.020),
+    (0xAA, 1.000, 0.050),
+    (0xC0, 2.100, 0.100),
+    (0xC9, 3.000, 0.200),
+    (0xEC, 10.000, 0.500),
+    (0xFE, 19.000, 1.000),
+];
+
+/// Returns the table value of a motor parameter code, or `None` for self measurement (code 0).
+pub(super) fn motor_param_value(code: u8) -> Option<f32> {
+    MOTOR_PARAM_SEGMENTS
+        .iter()
+        .rev()
+        .find(|(start, _, _)| code >= *start)
+        .map(|(start, base, step)| base + (code - start) as f32 * step)
+}
+
+/// Returns the code whose table value is nearest to `value`, along with that table value.
+/// Never returns code 0.
+pub(super) fn motor_param_code(value: f32) -> (u8, f32) {
+    let mut best = (1u8, MOTOR_PARAM_SEGMENTS[0].1);
+    for code in 2..=u8::MAX {
+        if let Some(table_value) = motor_param_value(code)
+            && (table_value - value).abs() < (best.1 - value).abs()
+        {
+            best = (code, table_value);
+        }
+    }
+    best
+}
+
+impl MotorResistance {
+    /// Smallest representable phase resistance in Ω.
+    pub const MIN_OHMS: f32 = 0.006;
+    /// Largest representable phase resistance in Ω.
+    pub const MAX_OHMS: f32 = 20.0;
+
+    /// Returns the code nearest to the given phase resistance in Ω.
+    /// `SelfMeasurement` is never selected.
+    pub fn from_ohms(ohms: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        OutOfRangeError::check(ohms, Self::MIN_OHMS, Self::MAX_OHMS)?;
+        let (code, actual) = motor_param_code(ohms);
+        Ok(Quantized {
+            value: MotorResistance::from(code),
+            requested: ohms,
+            actual,
+        })
+    }
+
+    /// Returns the phase resistance in Ω, or `None` for `SelfMeasurement`.
+    pub fn to_ohms(&self) -> Option<f32> {
+        motor_param_value(*self as u8)
+    }
+}
+
+impl MotorInductance {
+    /// Smallest representable phase inductance in H.
+    pub const MIN_HENRIES: f32 = 0.000_006;
+    /// Largest representable phase inductance in H.
+    pub const MAX_HENRIES: f32 = 0.020;
+
+    /// Returns the code nearest to the given phase inductance in H.
+    /// `SelfMeasurement` is never selected.
+    pub fn from_henries(henries: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        OutOfRangeError::check(henries, Self::MIN_HENRIES, Self::MAX_HENRIES)?;
+        let (code, millihenries) = motor_param_code(henries * 1000.0);
+        Ok(Quantized {
+            value: MotorInductance::from(code),
+            requested: henries,
+            actual: millihenries / 1000.0,
+        })
+    }
+
+    /// Returns the phase inductance in H, or `None` for `SelfMeasurement`.
+    pub fn to_henries(&self) -> Option<f32> {
+        motor_param_value(*self as u8).map(|millihenries| millihenries / 1000.0)
+    }
+}
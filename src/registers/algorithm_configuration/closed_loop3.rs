@@ -3,6 +3,7 @@
 use super::*;
 use arbitrary_int::*;
 use bitbybit::*;
+use core::num::NonZeroU8;
 
 /// Register to configure close loop settings3
 #[bitfield(u32, debug, default = 0x0)]
@@ -815,3 +816,74 @@ impl From<u8> for MotorBemf {
         unsafe { core::mem::transmute(value) }
     }
 }
+
+impl MotorBemf {
+    /// Smallest representable BEMF constant in mV/Hz.
+    pub const MIN_MV_PER_HZ: f32 = 0.6;
+    /// Largest representable BEMF constant in mV/Hz.
+    pub const MAX_MV_PER_HZ: f32 = 2000.0;
+
+    /// Returns the code nearest to the given line-to-line peak BEMF constant in mV per
+    /// electrical Hz, which is the unit used by Table 7-4.
+    /// `SelfMeasurement` is never selected.
+    pub fn from_mv_per_hz(mv_per_hz: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        OutOfRangeError::check(mv_per_hz, Self::MIN_MV_PER_HZ, Self::MAX_MV_PER_HZ)?;
+        let (code, actual) = super::closed_loop2::motor_param_code(mv_per_hz / 100.0);
+        Ok(Quantized {
+            value: MotorBemf::from(code),
+            requested: mv_per_hz,
+            actual: actual * 100.0,
+        })
+    }
+
+    /// Returns the BEMF constant in mV per electrical Hz, or `None` for `SelfMeasurement`.
+    pub fn to_mv_per_hz(&self) -> Option<f32> {
+        super::closed_loop2::motor_param_value(*self as u8).map(|value| value * 100.0)
+    }
+
+    /// Returns the code nearest to the given BEMF constant in mV per mechanical Hz.
+    ///
+    /// The value is divided by `pole_pairs` to get the electrical constant used by the device.
+    /// The range is checked against the electrical value, so `OutOfRangeError` reports mV per
+    /// electrical Hz.
+    pub fn from_mechanical_mv_per_hz(
+        mv_per_hz: f32,
+        pole_pairs: NonZeroU8,
+    ) -> Result<Quantized<Self>, OutOfRangeError> {
+        let quantized = Self::from_mv_per_hz(mv_per_hz / pole_pairs.get() as f32)?;
+        Ok(Quantized {
+            value: quantized.value,
+            requested: mv_per_hz,
+            actual: quantized.actual * pole_pairs.get() as f32,
+        })
+    }
+
+    /// Returns the BEMF constant in mV per mechanical Hz, or `None` for `SelfMeasurement`.
+    pub fn to_mechanical_mv_per_hz(&self, pole_pairs: NonZeroU8) -> Option<f32> {
+        self.to_mv_per_hz()
+            .map(|value| value * pole_pairs.get() as f32)
+    }
+
+    /// Returns the code nearest to the given BEMF constant in V/krpm (mechanical).
+    ///
+    /// 1 V/krpm equals 60 mV per mechanical Hz, which is then divided by `pole_pairs`.
+    /// The range is checked against the electrical value, so `OutOfRangeError` reports mV per
+    /// electrical Hz.
+    pub fn from_v_per_krpm(
+        v_per_krpm: f32,
+        pole_pairs: NonZeroU8,
+    ) -> Result<Quantized<Self>, OutOfRangeError> {
+        let quantized = Self::from_mechanical_mv_per_hz(v_per_krpm * 60.0, pole_pairs)?;
+        Ok(Quantized {
+            value: quantized.value,
+            requested: v_per_krpm,
+            actual: quantized.actual / 60.0,
+        })
+    }
+
+    /// Returns the BEMF constant in V/krpm (mechanical), or `None` for `SelfMeasurement`.
+    pub fn to_v_per_krpm(&self, pole_pairs: NonZeroU8) -> Option<f32> {
+        self.to_mechanical_mv_per_hz(pole_pairs)
+            .map(|value| value / 60.0)
+    }
+}
@@ -3,6 +3,7 @@
 use super::*;
 use arbitrary_int::*;
 use bitbybit::*;
+use core::num::NonZeroU8;
 
 /// Register to configure close loop settings4
 #[bitfield(u32, debug, default = 0x0)]
@@ -32,3 +33,50 @@ impl Register for ClosedLoop4 {
         Self::new_with_raw_value(value)
     }
 }
+
+impl ClosedLoop4 {
+    /// Resolution of `max_speed` in Hz.
+    pub const MAX_SPEED_RESOLUTION_HZ: f32 = 1.0 / 6.0;
+    /// Largest maximum speed that fits in the 14-bit field, in Hz.
+    pub const MAX_SPEED_LIMIT_HZ: f32 = u14::MAX.value() as f32 / 6.0;
+
+    /// Returns the maximum speed in electrical Hz.
+    pub fn max_speed_hz(&self) -> f32 {
+        self.max_speed().value() as f32 / 6.0
+    }
+
+    /// Sets the maximum speed in electrical Hz, rounded to the nearest 1/6 Hz.
+    /// Returns the encoded field along with the speed actually configured.
+    pub fn set_max_speed_hz(&mut self, hz: f32) -> Result<Quantized<u14>, OutOfRangeError> {
+        OutOfRangeError::check(hz, 0.0, Self::MAX_SPEED_LIMIT_HZ)?;
+        let max_speed = u14::new((hz * 6.0 + 0.5) as u16);
+        self.set_max_speed(max_speed);
+        Ok(Quantized {
+            value: max_speed,
+            requested: hz,
+            actual: self.max_speed_hz(),
+        })
+    }
+
+    /// Returns the maximum speed in mechanical RPM for a motor with the given number of pole pairs.
+    pub fn max_speed_rpm(&self, pole_pairs: NonZeroU8) -> f32 {
+        self.max_speed_hz() * 60.0 / pole_pairs.get() as f32
+    }
+
+    /// Sets the maximum speed in mechanical RPM for a motor with the given number of pole pairs.
+    /// Returns the encoded field along with the speed actually configured, in RPM.
+    ///
+    /// The range is checked against the electrical speed, so `OutOfRangeError` reports Hz.
+    pub fn set_max_speed_rpm(
+        &mut self,
+        rpm: f32,
+        pole_pairs: NonZeroU8,
+    ) -> Result<Quantized<u14>, OutOfRangeError> {
+        let quantized = self.set_max_speed_hz(rpm * pole_pairs.get() as f32 / 60.0)?;
+        Ok(Quantized {
+            value: quantized.value,
+            requested: rpm,
+            actual: self.max_speed_rpm(pole_pairs),
+        })
+    }
+}
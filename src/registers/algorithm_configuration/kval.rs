@@ -1,5 +1,6 @@
 //! Kx values for PI loops
 
+use super::*;
 use arbitrary_int::*;
 use bitbybit::*;
 
@@ -17,7 +18,7 @@ pub type SpeedKpVal = KVal<SpeedKp>;
 pub type SpeedKiVal = KVal<SpeedKi>;
 
 /// 10-bit value for PI loop. K_x = Multiplier (constant per field) * value / 10^scale
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct KVal<T: KValMultiplier> {
     inner: u10,
     _marker: core::marker::PhantomData<T>,
@@ -69,9 +70,57 @@ impl<T: KValMultiplier> KVal<T> {
     /// Returns the value represented by the internal bits.
     pub fn calculated_value(&self) -> f32 {
         let value = self.value() as f32;
-        let scale = 10f32.powi(self.scale().value() as i32 + T::SCALE_SHIFT as i32);
+        let scale = pow10(self.scale().value() as i32 + T::SCALE_SHIFT as i32);
         value / scale
     }
+
+    /// Smallest non-zero gain that can be represented.
+    pub fn min_gain() -> f32 {
+        1.0 / pow10(3 + T::SCALE_SHIFT as i32)
+    }
+
+    /// Largest gain that can be represented.
+    pub fn max_gain() -> f32 {
+        u8::MAX as f32 / pow10(T::SCALE_SHIFT as i32)
+    }
+
+    /// Encodes the given gain, choosing the scale and value bits with the smallest error.
+    /// Returns the encoded value along with the gain it actually represents.
+    ///
+    /// A gain of 0 cannot be requested this way since it would select `AUTO`.
+    pub fn from_gain(gain: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        OutOfRangeError::check(gain, Self::min_gain(), Self::max_gain())?;
+        let mut best = Self::AUTO;
+        let mut best_error = f32::INFINITY;
+        for scale in 0..=3u8 {
+            let exact = gain * pow10(scale as i32 + T::SCALE_SHIFT as i32);
+            if exact > u8::MAX as f32 + 0.5 {
+                continue;
+            }
+            let mut candidate = Self::AUTO;
+            candidate.set_scale(u2::new(scale));
+            candidate.set_value(((exact + 0.5) as u8).max(1));
+            let error = (candidate.calculated_value() - gain).abs();
+            if error < best_error {
+                best = candidate;
+                best_error = error;
+            }
+        }
+        Ok(Quantized {
+            value: best,
+            requested: gain,
+            actual: best.calculated_value(),
+        })
+    }
+}
+
+/// Returns 10 raised to the given power.
+fn pow10(exponent: i32) -> f32 {
+    let mut result = 1.0;
+    for _ in 0..exponent.unsigned_abs() {
+        result *= 10.0;
+    }
+    if exponent < 0 { 1.0 / result } else { result }
 }
 
 /// Unit struct for the constant multiplier of the Kp for the current loop.
@@ -133,4 +182,12 @@ impl KVal<SpeedKp> {
             0,
         ))
     }
+
+    /// Splits the speed loop Kp into its high and low parts for `ClosedLoop3` and `ClosedLoop4`.
+    pub fn to_high_low(&self) -> (SpeedLoopKpHigh3, SpeedLoopKpLow7) {
+        let raw = self.raw_value().value();
+        let high = SpeedLoopKpHigh3::new_with_raw_value(u3::extract_u16(raw, 7));
+        let low = SpeedLoopKpLow7::new_with_raw_value(u7::extract_u16(raw, 0));
+        (high, low)
+    }
 }
@@ -8,13 +8,17 @@ pub mod isd_config;
 pub mod kval;
 pub mod motor_startup1;
 pub mod motor_startup2;
+pub mod pi_gains;
 pub mod ref_profiles1;
 pub mod ref_profiles2;
 pub mod ref_profiles3;
 pub mod ref_profiles4;
 pub mod ref_profiles5;
 pub mod ref_profiles6;
+pub mod reference_profile;
 pub mod rev_drive_config;
+pub mod startup;
+pub mod startup_timeline;
 
 pub use super::addresses::*;
 pub use super::common::*;
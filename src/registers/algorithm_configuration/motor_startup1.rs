@@ -141,6 +141,18 @@ pub enum CurrentRampRate {
     NoLimit = 0xF,
 }
 
+impl CurrentRampRate {
+    const AMPS_PER_SECOND: [f32; 15] = [
+        0.1, 1.0, 5.0, 10.0, 15.0, 25.0, 50.0, 100.0, 150.0, 200.0, 250.0, 500.0, 1000.0, 2000.0,
+        5000.0,
+    ];
+
+    /// Returns the ramp rate in A/s, or `None` if there is no limit.
+    pub fn to_amps_per_second(&self) -> Option<f32> {
+        Self::AMPS_PER_SECOND.get(*self as usize).copied()
+    }
+}
+
 /// Align time
 #[bitenum(u4, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -195,6 +207,17 @@ pub enum AlignTime {
     S10 = 0xF,
 }
 
+impl AlignTime {
+    const SECONDS: [f32; 16] = [
+        0.01, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 4.0, 5.0, 7.5, 10.0,
+    ];
+
+    /// Returns the align time in seconds.
+    pub fn to_seconds(&self) -> f32 {
+        Self::SECONDS[*self as usize]
+    }
+}
+
 /// IPD Clock Frequency
 #[bitenum(u3, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -225,6 +248,15 @@ pub enum IpdClockFreq {
     Hz10000 = 0x7,
 }
 
+impl IpdClockFreq {
+    const HZ: [f32; 8] = [50.0, 100.0, 250.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0];
+
+    /// Returns the IPD clock frequency in Hz.
+    pub fn to_hz(&self) -> f32 {
+        Self::HZ[*self as usize]
+    }
+}
+
 /// Initial Position Detection (IPD) Current Threshold
 #[bitenum(u5, exhaustive = false)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
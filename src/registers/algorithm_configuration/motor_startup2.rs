@@ -154,6 +154,19 @@ pub enum OpenCloseLoopHandoffThreshold {
     P50 = 0x1F,
 }
 
+impl OpenCloseLoopHandoffThreshold {
+    const PERCENT: [f32; 32] = [
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        17.0, 18.0, 19.0, 20.0, 22.5, 25.0, 27.5, 30.0, 32.5, 35.0, 37.5, 40.0, 42.5, 45.0, 47.5,
+        50.0,
+    ];
+
+    /// Returns the handoff threshold in % of MAX_SPEED.
+    pub fn to_percent(&self) -> f32 {
+        Self::PERCENT[*self as usize]
+    }
+}
+
 // I have no idea why they did it this way
 /// Align angle
 #[bitenum(u5, exhaustive = false)]
@@ -303,6 +316,17 @@ pub enum SlowFirstCycleFrequency {
     P50 = 0xF,
 }
 
+impl SlowFirstCycleFrequency {
+    const PERCENT: [f32; 16] = [
+        1.0, 2.0, 3.0, 5.0, 7.5, 10.0, 12.5, 15.0, 17.5, 20.0, 25.0, 30.0, 35.0, 40.0, 45.0, 50.0,
+    ];
+
+    /// Returns the first cycle frequency in % of MAX_SPEED.
+    pub fn to_percent(&self) -> f32 {
+        Self::PERCENT[*self as usize]
+    }
+}
+
 /// Ramp rate for reducing difference between estimated theta and open
 /// loop theta
 #[bitenum(u3, exhaustive = true)]
@@ -0,0 +1,56 @@
+//! Current and speed loop PI gains, spread across `ClosedLoop3` and `ClosedLoop4`
+//!
+//! Section 7.7.1.7 and 7.7.1.8
+
+use super::closed_loop3::ClosedLoop3;
+use super::closed_loop4::ClosedLoop4;
+use super::*;
+
+/// Combined view of the PI loop gains.
+///
+/// The current loop gains live in `ClosedLoop3`, the speed loop Ki lives in `ClosedLoop4`, and the
+/// speed loop Kp is split between the two (3 MSB in `ClosedLoop3`, 7 LSB in `ClosedLoop4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PiGains {
+    /// Kp for the current Iq and Id loop.
+    pub current_kp: CurrentKpVal,
+    /// Ki for the current Iq and Id loop.
+    pub current_ki: CurrentKiVal,
+    /// Kp for the speed loop.
+    pub speed_kp: SpeedKpVal,
+    /// Ki for the speed loop.
+    pub speed_ki: SpeedKiVal,
+}
+
+impl PiGains {
+    /// All gains determined automatically by the processor.
+    pub const AUTO: Self = PiGains {
+        current_kp: CurrentKpVal::AUTO,
+        current_ki: CurrentKiVal::AUTO,
+        speed_kp: SpeedKpVal::AUTO,
+        speed_ki: SpeedKiVal::AUTO,
+    };
+
+    /// Reads the gains out of the two closed loop registers.
+    pub fn from_registers(closed_loop3: &ClosedLoop3, closed_loop4: &ClosedLoop4) -> Self {
+        PiGains {
+            current_kp: closed_loop3.curr_loop_kp(),
+            current_ki: closed_loop3.curr_loop_ki(),
+            speed_kp: SpeedKpVal::from_high_low(
+                closed_loop3.spd_loop_kp(),
+                closed_loop4.spd_loop_kp(),
+            ),
+            speed_ki: closed_loop4.spd_loop_ki(),
+        }
+    }
+
+    /// Writes the gains into the two closed loop registers, leaving all other fields untouched.
+    pub fn apply(&self, closed_loop3: &mut ClosedLoop3, closed_loop4: &mut ClosedLoop4) {
+        let (speed_kp_high, speed_kp_low) = self.speed_kp.to_high_low();
+        closed_loop3.set_curr_loop_kp(self.current_kp);
+        closed_loop3.set_curr_loop_ki(self.current_ki);
+        closed_loop3.set_spd_loop_kp(speed_kp_high);
+        closed_loop4.set_spd_loop_kp(speed_kp_low);
+        closed_loop4.set_spd_loop_ki(self.speed_ki);
+    }
+}
@@ -0,0 +1,593 @@
+//! Speed reference profile, spread across `RefProfiles1` through `RefProfiles6`
+//!
+//! Section 7.7.1.9 through 7.7.1.14
+
+use super::closed_loop4::ClosedLoop4;
+use super::ref_profiles1::{RefProfileConfig, RefProfiles1};
+use super::ref_profiles2::RefProfiles2;
+use super::ref_profiles3::{DutyHysteresis, RefProfiles3};
+use super::ref_profiles4::RefProfiles4;
+use super::ref_profiles5::RefProfiles5;
+use super::ref_profiles6::RefProfiles6;
+use super::*;
+
+/// The six reference profile registers, in address order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RefProfileRegisters {
+    /// Reference Profile Configuration 1
+    pub ref_profiles1: RefProfiles1,
+    /// Reference Profile Configuration 2
+    pub ref_profiles2: RefProfiles2,
+    /// Reference Profile Configuration 3
+    pub ref_profiles3: RefProfiles3,
+    /// Reference Profile Configuration 4
+    pub ref_profiles4: RefProfiles4,
+    /// Reference Profile Configuration 5
+    pub ref_profiles5: RefProfiles5,
+    /// Reference Profile Configuration 6
+    pub ref_profiles6: RefProfiles6,
+}
+
+/// Turn on, turn off and clamp settings of one half of a reference profile.
+///
+/// Linear and staircase profiles only use the first set. Forward-reverse profiles use the first
+/// set for the reverse direction and the second set for the forward direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileLimits {
+    /// Turn On Duty Cycle
+    pub duty_on: PercentAsU8,
+    /// Turn Off Duty Cycle
+    pub duty_off: PercentAsU8,
+    /// Duty Cycle for clamping Duty Input
+    pub duty_clamp: PercentAsU8,
+    /// Turn off reference (% of Maximum Reference)
+    pub ref_off: PercentAsU8,
+    /// Clamp reference (% of Maximum Reference)
+    pub ref_clamp: PercentAsU8,
+}
+
+/// Complete speed-vs-duty reference profile.
+///
+/// Converts to and from `RefProfileRegisters`, taking care of the duty A, duty E and
+/// reference B values that are split across register boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceProfile {
+    /// Configuration for reference profiles
+    pub mode: RefProfileConfig,
+    /// Duty cycle points A through E
+    pub duty: [PercentAsU8; 5],
+    /// Reference points A through E (% of Maximum Reference)
+    pub reference: [PercentAsU8; 5],
+    /// Settings stored in `RefProfiles1` and `RefProfiles4`
+    pub limits1: ProfileLimits,
+    /// Settings stored in `RefProfiles3` and `RefProfiles6`
+    pub limits2: ProfileLimits,
+    /// Duty hysteresis
+    pub duty_hys: DutyHysteresis,
+}
+
+impl From<RefProfileRegisters> for ReferenceProfile {
+    fn from(registers: RefProfileRegisters) -> Self {
+        let RefProfileRegisters {
+            ref_profiles1: r1,
+            ref_profiles2: r2,
+            ref_profiles3: r3,
+            ref_profiles4: r4,
+            ref_profiles5: r5,
+            ref_profiles6: r6,
+        } = registers;
+        ReferenceProfile {
+            mode: r1.ref_profile_config(),
+            duty: [
+                PercentAsU8::combine_duty_a(r1.duty_a(), r2.duty_a()),
+                r2.duty_b(),
+                r2.duty_c(),
+                r2.duty_d(),
+                PercentAsU8::combine_duty_e(r2.duty_e(), r3.duty_e()),
+            ],
+            reference: [
+                r4.ref_a(),
+                PercentAsU8::combine_ref_b(r4.ref_b(), r5.ref_b()),
+                r5.ref_c(),
+                r5.ref_d(),
+                r5.ref_e(),
+            ],
+            limits1: ProfileLimits {
+                duty_on: r1.duty_on1(),
+                duty_off: r1.duty_off1(),
+                duty_clamp: r1.duty_clamp1(),
+                ref_off: r4.ref_off1(),
+                ref_clamp: r4.ref_clamp1(),
+            },
+            limits2: ProfileLimits {
+                duty_on: r3.duty_on2(),
+                duty_off: r3.duty_off2(),
+                duty_clamp: r3.duty_clamp2(),
+                ref_off: r6.ref_off2(),
+                ref_clamp: r6.ref_clamp2(),
+            },
+            duty_hys: r3.duty_hys(),
+        }
+    }
+}
+
+impl From<ReferenceProfile> for RefProfileRegisters {
+    fn from(profile: ReferenceProfile) -> Self {
+        let [duty_a, duty_b, duty_c, duty_d, duty_e] = profile.duty;
+        let [ref_a, ref_b, ref_c, ref_d, ref_e] = profile.reference;
+        let (duty_a_high, duty_a_low) = duty_a.split_duty_a();
+        let (duty_e_high, duty_e_low) = duty_e.split_duty_e();
+        let (ref_b_high, ref_b_low) = ref_b.split_ref_b();
+        RefProfileRegisters {
+            ref_profiles1: RefProfiles1::DEFAULT
+                .with_ref_profile_config(profile.mode)
+                .with_duty_on1(profile.limits1.duty_on)
+                .with_duty_off1(profile.limits1.duty_off)
+                .with_duty_clamp1(profile.limits1.duty_clamp)
+                .with_duty_a(duty_a_high),
+            ref_profiles2: RefProfiles2::DEFAULT
+                .with_duty_a(duty_a_low)
+                .with_duty_b(duty_b)
+                .with_duty_c(duty_c)
+                .with_duty_d(duty_d)
+                .with_duty_e(duty_e_high),
+            ref_profiles3: RefProfiles3::DEFAULT
+                .with_duty_e(duty_e_low)
+                .with_duty_on2(profile.limits2.duty_on)
+                .with_duty_off2(profile.limits2.duty_off)
+                .with_duty_clamp2(profile.limits2.duty_clamp)
+                .with_duty_hys(profile.duty_hys),
+            ref_profiles4: RefProfiles4::DEFAULT
+                .with_ref_off1(profile.limits1.ref_off)
+                .with_ref_clamp1(profile.limits1.ref_clamp)
+                .with_ref_a(ref_a)
+                .with_ref_b(ref_b_high),
+            ref_profiles5: RefProfiles5::DEFAULT
+                .with_ref_b(ref_b_low)
+                .with_ref_c(ref_c)
+                .with_ref_d(ref_d)
+                .with_ref_e(ref_e),
+            ref_profiles6: RefProfiles6::DEFAULT
+                .with_ref_off2(profile.limits2.ref_off)
+                .with_ref_clamp2(profile.limits2.ref_clamp),
+        }
+    }
+}
+
+/// Speed reference commanded by a reference profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedReference {
+    /// Reference in % of the maximum reference.
+    /// Negative values command the reverse direction (forward-reverse profiles only).
+    pub percent: f32,
+}
+
+impl SpeedReference {
+    /// Motor off.
+    pub const OFF: Self = SpeedReference { percent: 0.0 };
+
+    /// Returns true if the profile commands the motor off.
+    pub fn is_off(&self) -> bool {
+        self.percent == 0.0
+    }
+
+    /// Returns true if the profile commands the reverse direction.
+    pub fn is_reverse(&self) -> bool {
+        self.percent < 0.0
+    }
+
+    /// Converts the reference into electrical Hz using `ClosedLoop4::max_speed`.
+    pub fn hz(&self, closed_loop4: &ClosedLoop4) -> f32 {
+        self.percent / 100.0 * closed_loop4.max_speed_hz()
+    }
+}
+
+/// Motor state remembered between duty samples, used for turn on/off and staircase hysteresis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EvaluatorState {
+    Off,
+    Forward { step: Option<usize> },
+    Reverse,
+}
+
+/// Predicts the speed reference the device commands for a sequence of SPEED pin duty cycles.
+///
+/// The device remembers whether the motor is running, so the same duty cycle can produce
+/// different references depending on history. Feed duty samples in order through `update`.
+///
+/// The model follows the profile figures in section 7.3:
+/// - Reference/Equation: the reference follows the duty cycle directly.
+/// - Linear: the motor turns on at `duty_on` and off below `duty_off`. While running, the
+///   reference is `ref_off` below duty A, interpolated linearly between points A to E, `ref_e`
+///   above duty E and `ref_clamp` at or above `duty_clamp`.
+/// - Staircase: as linear, but each point holds its reference until the next point. Stepping
+///   down a stair requires the duty to drop `duty_hys` below the stair's duty point.
+/// - Forward-reverse: low duty cycles drive in reverse using `limits1` and points A and B, high
+///   duty cycles drive forward using `limits2` and points C to E. Reverse turns on at or below
+///   `limits1.duty_on` and off above `limits1.duty_off`; forward turns on at or above
+///   `limits2.duty_on` and off below `limits2.duty_off`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileEvaluator {
+    profile: ReferenceProfile,
+    state: EvaluatorState,
+}
+
+impl ProfileEvaluator {
+    /// Creates an evaluator for the given profile with the motor stopped.
+    pub fn new(profile: ReferenceProfile) -> Self {
+        ProfileEvaluator {
+            profile,
+            state: EvaluatorState::Off,
+        }
+    }
+
+    /// Returns the profile being evaluated.
+    pub fn profile(&self) -> &ReferenceProfile {
+        &self.profile
+    }
+
+    /// Applies the next SPEED pin duty cycle and returns the commanded reference.
+    pub fn update(&mut self, duty: PercentAsU8) -> SpeedReference {
+        match self.profile.mode {
+            RefProfileConfig::RefEq => SpeedReference {
+                percent: f32::from(duty),
+            },
+            RefProfileConfig::Linear | RefProfileConfig::Staircase => self.update_single(duty),
+            RefProfileConfig::ForwardReverse => self.update_forward_reverse(duty),
+        }
+    }
+
+    fn update_single(&mut self, duty: PercentAsU8) -> SpeedReference {
+        let limits = self.profile.limits1;
+        let step = match self.state {
+            EvaluatorState::Off if duty >= limits.duty_on => None,
+            EvaluatorState::Off | EvaluatorState::Reverse => return self.stop(),
+            EvaluatorState::Forward { .. } if duty < limits.duty_off => return self.stop(),
+            EvaluatorState::Forward { step } => step,
+        };
+        let step = match self.profile.mode {
+            RefProfileConfig::Staircase => self.stair(duty, step),
+            _ => self.stair(duty, None),
+        };
+        self.state = EvaluatorState::Forward { step };
+
+        let percent = if duty >= limits.duty_clamp {
+            f32::from(limits.ref_clamp)
+        } else {
+            match (self.profile.mode, step) {
+                (_, None) => f32::from(limits.ref_off),
+                (RefProfileConfig::Staircase, Some(step)) => {
+                    f32::from(self.profile.reference[step])
+                }
+                (_, Some(step)) => self.interpolate(duty, step),
+            }
+        };
+        SpeedReference { percent }
+    }
+
+    fn update_forward_reverse(&mut self, duty: PercentAsU8) -> SpeedReference {
+        let reverse = self.profile.limits1;
+        let forward = self.profile.limits2;
+        match self.state {
+            EvaluatorState::Reverse if duty > reverse.duty_off => self.state = EvaluatorState::Off,
+            EvaluatorState::Forward { .. } if duty < forward.duty_off => {
+                self.state = EvaluatorState::Off
+            }
+            _ => {}
+        }
+        if self.state == EvaluatorState::Off {
+            if duty <= reverse.duty_on {
+                self.state = EvaluatorState::Reverse;
+            } else if duty >= forward.duty_on {
+                self.state = EvaluatorState::Forward { step: None };
+            }
+        }
+
+        let [duty_a, duty_b, duty_c, duty_d, duty_e] = self.profile.duty;
+        let [ref_a, _, _, _, ref_e] = self.profile.reference;
+        let percent = match self.state {
+            EvaluatorState::Off => 0.0,
+            EvaluatorState::Reverse => {
+                let magnitude = if duty < reverse.duty_clamp {
+                    f32::from(reverse.ref_clamp)
+                } else if duty < duty_a {
+                    f32::from(ref_a)
+                } else if duty < duty_b {
+                    self.interpolate(duty, 0)
+                } else {
+                    f32::from(reverse.ref_off)
+                };
+                -magnitude
+            }
+            EvaluatorState::Forward { .. } => {
+                if duty >= forward.duty_clamp {
+                    f32::from(forward.ref_clamp)
+                } else if duty >= duty_e {
+                    f32::from(ref_e)
+                } else if duty >= duty_c {
+                    self.interpolate(duty, if duty >= duty_d { 3 } else { 2 })
+                } else {
+                    f32::from(forward.ref_off)
+                }
+            }
+        };
+        SpeedReference { percent }
+    }
+
+    fn stop(&mut self) -> SpeedReference {
+        self.state = EvaluatorState::Off;
+        SpeedReference::OFF
+    }
+
+    /// Returns the highest point at or below `duty`, allowing the current `step` to be held
+    /// until the duty drops below its point by the hysteresis.
+    fn stair(&self, duty: PercentAsU8, step: Option<usize>) -> Option<usize> {
+        let duty = f32::from(duty);
+        let hysteresis = match self.profile.duty_hys {
+            DutyHysteresis::P0 => 0.0,
+            DutyHysteresis::P0_5 => 0.5,
+            DutyHysteresis::P1 => 1.0,
+            DutyHysteresis::P2 => 2.0,
+        };
+        self.profile
+            .duty
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, point)| {
+                let held = step.is_some_and(|step| index <= step);
+                let threshold = f32::from(*point) - if held { hysteresis } else { 0.0 };
+                (duty >= threshold).then_some(index)
+            })
+    }
+
+    /// Interpolates between point `index` and the next point, holding the last point.
+    fn interpolate(&self, duty: PercentAsU8, index: usize) -> f32 {
+        let duty = f32::from(duty);
+        let Some((next_duty, next_ref)) = self
+            .profile
+            .duty
+            .get(index + 1)
+            .zip(self.profile.reference.get(index + 1))
+        else {
+            return f32::from(self.profile.reference[index]);
+        };
+        let (duty0, ref0) = (
+            f32::from(self.profile.duty[index]),
+            f32::from(self.profile.reference[index]),
+        );
+        let (duty1, ref1) = (f32::from(*next_duty), f32::from(*next_ref));
+        if duty >= duty1 || duty1 <= duty0 {
+            ref1
+        } else {
+            ref0 + (duty - duty0) * (ref1 - ref0) / (duty1 - duty0)
+        }
+    }
+}
+
+impl ReferenceProfile {
+    /// Returns an evaluator for this profile with the motor stopped.
+    pub fn evaluator(&self) -> ProfileEvaluator {
+        ProfileEvaluator::new(*self)
+    }
+
+    /// Returns the reference for every duty cycle code while sweeping the duty up from 0%,
+    /// starting with the motor stopped. Useful for plotting the profile.
+    pub fn rising_curve(&self) -> impl Iterator<Item = (PercentAsU8, SpeedReference)> {
+        let mut evaluator = self.evaluator();
+        (0..=u8::MAX).map(move |raw| {
+            let duty = PercentAsU8::new(raw);
+            (duty, evaluator.update(duty))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn percent(raw: u8) -> PercentAsU8 {
+        PercentAsU8::new(raw)
+    }
+
+    fn linear_profile() -> ReferenceProfile {
+        ReferenceProfile {
+            mode: RefProfileConfig::Linear,
+            duty: [
+                percent(51),
+                percent(102),
+                percent(153),
+                percent(204),
+                percent(230),
+            ],
+            reference: [
+                percent(51),
+                percent(102),
+                percent(153),
+                percent(204),
+                percent(255),
+            ],
+            limits1: ProfileLimits {
+                duty_on: percent(40),
+                duty_off: percent(30),
+                duty_clamp: percent(250),
+                ref_off: percent(25),
+                ref_clamp: percent(240),
+            },
+            limits2: ProfileLimits {
+                duty_on: percent(0),
+                duty_off: percent(0),
+                duty_clamp: percent(0),
+                ref_off: percent(0),
+                ref_clamp: percent(0),
+            },
+            duty_hys: DutyHysteresis::P0,
+        }
+    }
+
+    /// Profile using every field, with the split duty A, duty E and reference B values set on
+    /// both sides of their register boundaries.
+    fn split_profile() -> ReferenceProfile {
+        ReferenceProfile {
+            mode: RefProfileConfig::ForwardReverse,
+            duty: [
+                percent(0xA5),
+                percent(0x11),
+                percent(0x22),
+                percent(0x33),
+                percent(0xA5),
+            ],
+            reference: [
+                percent(0x44),
+                percent(0xA5),
+                percent(0x55),
+                percent(0x66),
+                percent(0x77),
+            ],
+            limits1: ProfileLimits {
+                duty_on: percent(0x18),
+                duty_off: percent(0x10),
+                duty_clamp: percent(0xF0),
+                ref_off: percent(0x20),
+                ref_clamp: percent(0xE0),
+            },
+            limits2: ProfileLimits {
+                duty_on: percent(0x28),
+                duty_off: percent(0x1C),
+                duty_clamp: percent(0xE8),
+                ref_off: percent(0x30),
+                ref_clamp: percent(0xD0),
+            },
+            duty_hys: DutyHysteresis::P0_5,
+        }
+    }
+
+    /// Passes every register through its bus value, as a write followed by a read would.
+    fn over_the_bus(registers: RefProfileRegisters) -> RefProfileRegisters {
+        fn reload<T: Register>(register: T) -> T {
+            T::from_value(register.value())
+        }
+        RefProfileRegisters {
+            ref_profiles1: reload(registers.ref_profiles1),
+            ref_profiles2: reload(registers.ref_profiles2),
+            ref_profiles3: reload(registers.ref_profiles3),
+            ref_profiles4: reload(registers.ref_profiles4),
+            ref_profiles5: reload(registers.ref_profiles5),
+            ref_profiles6: reload(registers.ref_profiles6),
+        }
+    }
+
+    /// Returns the registers that change when `edit` is applied to `split_profile`.
+    fn changed_registers(edit: impl FnOnce(&mut ReferenceProfile)) -> [bool; 6] {
+        let before = RefProfileRegisters::from(split_profile());
+        let mut profile = split_profile();
+        edit(&mut profile);
+        let after = RefProfileRegisters::from(profile);
+        [
+            before.ref_profiles1 != after.ref_profiles1,
+            before.ref_profiles2 != after.ref_profiles2,
+            before.ref_profiles3 != after.ref_profiles3,
+            before.ref_profiles4 != after.ref_profiles4,
+            before.ref_profiles5 != after.ref_profiles5,
+            before.ref_profiles6 != after.ref_profiles6,
+        ]
+    }
+
+    fn assert_reference(reference: SpeedReference, raw: u8) {
+        let expected = f32::from(percent(raw));
+        assert!(
+            (reference.percent - expected).abs() < 1e-3,
+            "expected {expected}%, got {}%",
+            reference.percent
+        );
+    }
+
+    #[test]
+    fn interpolates_exactly_at_breakpoints() {
+        let mut evaluator = linear_profile().evaluator();
+        for raw in [51, 102, 153, 204] {
+            assert_reference(evaluator.update(percent(raw)), raw);
+        }
+    }
+
+    #[test]
+    fn interpolates_between_breakpoints() {
+        let mut evaluator = linear_profile().evaluator();
+        // Duty and reference points are equal, so the reference follows the duty cycle.
+        for raw in [77, 128, 180] {
+            assert_reference(evaluator.update(percent(raw)), raw);
+        }
+    }
+
+    #[test]
+    fn clamps_below_range() {
+        let mut evaluator = linear_profile().evaluator();
+        assert!(evaluator.update(percent(35)).is_off());
+        assert_reference(evaluator.update(percent(45)), 25);
+        assert_reference(evaluator.update(percent(35)), 25);
+        assert!(evaluator.update(percent(29)).is_off());
+    }
+
+    #[test]
+    fn clamps_above_range() {
+        let mut evaluator = linear_profile().evaluator();
+        assert_reference(evaluator.update(percent(240)), 255);
+        assert_reference(evaluator.update(percent(250)), 240);
+        assert_reference(evaluator.update(percent(255)), 240);
+    }
+
+    #[test]
+    fn rising_curve_shape() {
+        let curve: [(PercentAsU8, SpeedReference); 256] = {
+            let mut curve = [(percent(0), SpeedReference::OFF); 256];
+            for (slot, point) in curve.iter_mut().zip(linear_profile().rising_curve()) {
+                *slot = point;
+            }
+            curve
+        };
+        for (raw, (duty, _)) in curve.iter().enumerate() {
+            assert_eq!(duty.raw_value() as usize, raw);
+        }
+        assert!(curve[..40].iter().all(|(_, reference)| reference.is_off()));
+        assert_reference(curve[40].1, 25);
+        assert_reference(curve[51].1, 51);
+        assert_reference(curve[230].1, 255);
+        assert_reference(curve[255].1, 240);
+        assert!(
+            curve[40..250]
+                .windows(2)
+                .all(|pair| pair[1].1.percent >= pair[0].1.percent)
+        );
+    }
+
+    #[test]
+    fn registers_round_trip() {
+        let profile = split_profile();
+        let registers = over_the_bus(RefProfileRegisters::from(profile));
+        assert_eq!(ReferenceProfile::from(registers), profile);
+    }
+
+    #[test]
+    fn duty_a_splits_across_ref_profiles1_and_2() {
+        let high = changed_registers(|profile| profile.duty[0] = percent(0xA5 ^ 0xF8));
+        let low = changed_registers(|profile| profile.duty[0] = percent(0xA5 ^ 0x07));
+        assert_eq!(high, [true, false, false, false, false, false]);
+        assert_eq!(low, [false, true, false, false, false, false]);
+    }
+
+    #[test]
+    fn duty_e_splits_across_ref_profiles2_and_3() {
+        let high = changed_registers(|profile| profile.duty[4] = percent(0xA5 ^ 0xF0));
+        let low = changed_registers(|profile| profile.duty[4] = percent(0xA5 ^ 0x0F));
+        assert_eq!(high, [false, true, false, false, false, false]);
+        assert_eq!(low, [false, false, true, false, false, false]);
+    }
+
+    #[test]
+    fn ref_b_splits_across_ref_profiles4_and_5() {
+        let high = changed_registers(|profile| profile.reference[1] = percent(0xA5 ^ 0xFE));
+        let low = changed_registers(|profile| profile.reference[1] = percent(0xA5 ^ 0x01));
+        assert_eq!(high, [false, false, false, true, false, false]);
+        assert_eq!(low, [false, false, false, false, true, false]);
+    }
+}
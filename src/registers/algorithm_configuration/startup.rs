@@ -0,0 +1,236 @@
+//! Motor startup sequence, spread across `MotorStartup1` and `MotorStartup2`
+//!
+//! Section 7.7.1.3 and 7.7.1.4
+
+use super::motor_startup1::*;
+use super::motor_startup2::*;
+use super::*;
+use arbitrary_int::*;
+
+/// Parameters of the align and double align startup methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlignStartup {
+    /// Align time
+    pub align_time: AlignTime,
+    /// Align current limit
+    pub current_limit: CurrentSelection,
+    /// Align angle
+    pub align_angle: AlignAngle,
+    /// First cycle frequency in open loop after aligning, or `None` for 0 Hz
+    pub first_cycle_freq: Option<SlowFirstCycleFrequency>,
+}
+
+/// Parameters of the initial position detection startup method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpdStartup {
+    /// IPD clock frequency
+    pub clock_freq: IpdClockFreq,
+    /// IPD current threshold
+    pub current_threshold: IpdCurrentThreshold,
+    /// IPD release mode.
+    /// false = Brake, true = Tristate
+    pub release_tristate: bool,
+    /// IPD advance angle
+    pub advance_angle: IpdAdvanceAngle,
+    /// Number of times IPD is executed
+    pub repeat: u2,
+    /// First cycle frequency in open loop after IPD, or `None` for 0 Hz
+    pub first_cycle_freq: Option<SlowFirstCycleFrequency>,
+}
+
+/// Parameters of the slow first cycle startup method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowFirstCycleStartup {
+    /// Frequency of the first cycle
+    pub frequency: SlowFirstCycleFrequency,
+    /// Slow first cycle current limit
+    pub current_limit: CurrentSelection,
+}
+
+/// Startup method along with the parameters that apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupMethod {
+    /// `StartupMode::Align`
+    Align(AlignStartup),
+    /// `StartupMode::DoubleAlign`
+    DoubleAlign(AlignStartup),
+    /// `StartupMode::Ipd`
+    Ipd(IpdStartup),
+    /// `StartupMode::SlowFirstCycle`
+    SlowFirstCycle(SlowFirstCycleStartup),
+}
+
+impl StartupMethod {
+    /// Returns the `StartupMode` this method is encoded as.
+    pub fn mode(&self) -> StartupMode {
+        match self {
+            StartupMethod::Align(_) => StartupMode::Align,
+            StartupMethod::DoubleAlign(_) => StartupMode::DoubleAlign,
+            StartupMethod::Ipd(_) => StartupMode::Ipd,
+            StartupMethod::SlowFirstCycle(_) => StartupMode::SlowFirstCycle,
+        }
+    }
+}
+
+/// Open to closed loop handoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handoff {
+    /// The device determines the handoff speed automatically.
+    Auto,
+    /// Handoff at a fixed threshold (% of MAX_SPEED).
+    Threshold(OpenCloseLoopHandoffThreshold),
+}
+
+/// Complete startup sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartupSequence {
+    /// Startup method and its parameters
+    pub method: StartupMethod,
+    /// Align, slow first cycle, and open loop current ramp rate
+    pub ramp_rate: CurrentRampRate,
+    /// Open to closed loop handoff
+    pub handoff: Handoff,
+}
+
+/// Fields left in the startup registers that do not apply to the selected startup method.
+///
+/// The device ignores them, but they usually mean the configuration was only partly changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StartupLeftovers {
+    /// IPD fields are set while the method is not IPD.
+    pub ipd: bool,
+    /// Align time or align angle is set while the method is neither align nor double align.
+    pub align: bool,
+    /// The slow first cycle frequency is set while it is not used.
+    pub first_cycle_freq: bool,
+}
+
+impl StartupLeftovers {
+    /// Returns true if any leftover was found.
+    pub fn any(&self) -> bool {
+        self.ipd || self.align || self.first_cycle_freq
+    }
+}
+
+impl StartupSequence {
+    /// Reads the startup sequence out of the two startup registers.
+    ///
+    /// Returns `None` if a field used by the selected method holds a reserved value.
+    pub fn from_registers(
+        motor_startup1: &MotorStartup1,
+        motor_startup2: &MotorStartup2,
+    ) -> Option<Self> {
+        let first_cycle_freq = motor_startup2
+            .first_cycle_freq_sel()
+            .then(|| motor_startup2.slow_first_cyc_freq());
+        let align = || -> Option<AlignStartup> {
+            Some(AlignStartup {
+                align_time: motor_startup1.align_time(),
+                current_limit: motor_startup1.align_or_slow_current_ilimit(),
+                align_angle: motor_startup2.align_angle()?,
+                first_cycle_freq,
+            })
+        };
+        let method = match motor_startup1.mtr_startup() {
+            StartupMode::Align => StartupMethod::Align(align()?),
+            StartupMode::DoubleAlign => StartupMethod::DoubleAlign(align()?),
+            StartupMode::Ipd => StartupMethod::Ipd(IpdStartup {
+                clock_freq: motor_startup1.ipd_clk_freq(),
+                current_threshold: motor_startup1.ipd_curr_thr()?,
+                release_tristate: motor_startup1.ipd_rls_mode(),
+                advance_angle: motor_startup1.ipd_adv_angle(),
+                repeat: motor_startup1.ipd_repeat(),
+                first_cycle_freq,
+            }),
+            StartupMode::SlowFirstCycle => StartupMethod::SlowFirstCycle(SlowFirstCycleStartup {
+                frequency: motor_startup2.slow_first_cyc_freq(),
+                current_limit: motor_startup1.align_or_slow_current_ilimit(),
+            }),
+        };
+        let handoff = if motor_startup2.auto_handoff_en() {
+            Handoff::Auto
+        } else {
+            Handoff::Threshold(motor_startup2.opn_cl_handoff_thr())
+        };
+        Some(StartupSequence {
+            method,
+            ramp_rate: motor_startup1.align_slow_ramp_rate(),
+            handoff,
+        })
+    }
+
+    /// Writes the startup sequence into the two startup registers.
+    ///
+    /// Only the fields used by the selected method are written; the others keep their value.
+    /// Returns the fields that were left set but do not apply to the method.
+    pub fn apply(
+        &self,
+        motor_startup1: &mut MotorStartup1,
+        motor_startup2: &mut MotorStartup2,
+    ) -> StartupLeftovers {
+        motor_startup1.set_mtr_startup(self.method.mode());
+        motor_startup1.set_align_slow_ramp_rate(self.ramp_rate);
+        match self.handoff {
+            Handoff::Auto => motor_startup2.set_auto_handoff_en(true),
+            Handoff::Threshold(threshold) => {
+                motor_startup2.set_auto_handoff_en(false);
+                motor_startup2.set_opn_cl_handoff_thr(threshold);
+            }
+        }
+
+        let first_cycle_freq = match self.method {
+            StartupMethod::Align(align) | StartupMethod::DoubleAlign(align) => {
+                motor_startup1.set_align_time(align.align_time);
+                motor_startup1.set_align_or_slow_current_ilimit(align.current_limit);
+                motor_startup2.set_align_angle(Some(align.align_angle));
+                align.first_cycle_freq
+            }
+            StartupMethod::Ipd(ipd) => {
+                motor_startup1.set_ipd_clk_freq(ipd.clock_freq);
+                motor_startup1.set_ipd_curr_thr(Some(ipd.current_threshold));
+                motor_startup1.set_ipd_rls_mode(ipd.release_tristate);
+                motor_startup1.set_ipd_adv_angle(ipd.advance_angle);
+                motor_startup1.set_ipd_repeat(ipd.repeat);
+                ipd.first_cycle_freq
+            }
+            StartupMethod::SlowFirstCycle(slow) => {
+                motor_startup1.set_align_or_slow_current_ilimit(slow.current_limit);
+                Some(slow.frequency)
+            }
+        };
+        if let Some(frequency) = first_cycle_freq {
+            motor_startup2.set_slow_first_cyc_freq(frequency);
+        }
+        motor_startup2.set_first_cycle_freq_sel(
+            first_cycle_freq.is_some() && !matches!(self.method, StartupMethod::SlowFirstCycle(_)),
+        );
+
+        Self::leftovers(motor_startup1, motor_startup2)
+    }
+
+    /// Returns the fields set in the two startup registers that do not apply to the startup
+    /// method they select. A field counts as set when it differs from its reset value.
+    pub fn leftovers(
+        motor_startup1: &MotorStartup1,
+        motor_startup2: &MotorStartup2,
+    ) -> StartupLeftovers {
+        let reset1 = MotorStartup1::DEFAULT;
+        let reset2 = MotorStartup2::DEFAULT;
+        let mode = motor_startup1.mtr_startup();
+        let ipd_set = motor_startup1.ipd_clk_freq() != reset1.ipd_clk_freq()
+            || motor_startup1.ipd_curr_thr() != reset1.ipd_curr_thr()
+            || motor_startup1.ipd_rls_mode() != reset1.ipd_rls_mode()
+            || motor_startup1.ipd_adv_angle() != reset1.ipd_adv_angle()
+            || motor_startup1.ipd_repeat() != reset1.ipd_repeat();
+        let align_set = motor_startup1.align_time() != reset1.align_time()
+            || motor_startup2.align_angle() != reset2.align_angle();
+        let first_cycle_used =
+            mode == StartupMode::SlowFirstCycle || motor_startup2.first_cycle_freq_sel();
+        StartupLeftovers {
+            ipd: mode != StartupMode::Ipd && ipd_set,
+            align: !matches!(mode, StartupMode::Align | StartupMode::DoubleAlign) && align_set,
+            first_cycle_freq: !first_cycle_used
+                && motor_startup2.slow_first_cyc_freq() != reset2.slow_first_cyc_freq(),
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Estimate of the time the motor needs to reach speed from standstill
+//!
+//! The estimate follows the startup sequence: the startup method (align, double align, IPD or
+//! slow first cycle), the open loop ramp up to the handoff speed and the closed loop
+//! acceleration up to the target speed. It only reads register values.
+//!
+//! The IPD duration is an estimate. The datasheet gives no formula for it, so each IPD run is
+//! taken as twelve IPD clock periods: six voltage vectors, each driven and then released.
+
+use super::closed_loop1::*;
+use super::closed_loop4::*;
+use super::motor_startup1::*;
+use super::motor_startup2::*;
+use super::startup::*;
+use super::*;
+
+/// Estimated number of IPD clock periods taken by one IPD run (six vectors, each driven and
+/// released). Not given by the datasheet.
+const IPD_CLOCKS_PER_RUN: f32 = 12.0;
+
+/// Phase of the startup timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum StartupPhaseKind {
+    /// Rotor alignment
+    #[strum(to_string = "Align")]
+    Align,
+    /// Rotor alignment at two angles
+    #[strum(to_string = "Double Align")]
+    DoubleAlign,
+    /// Initial position detection
+    #[strum(to_string = "IPD")]
+    Ipd,
+    /// Slow first cycle
+    #[strum(to_string = "Slow First Cycle")]
+    SlowFirstCycle,
+    /// Open loop ramp up to the handoff speed
+    #[strum(to_string = "Open Loop")]
+    OpenLoop,
+    /// Closed loop acceleration up to the target speed
+    #[strum(to_string = "Closed Loop")]
+    ClosedLoop,
+}
+
+/// One phase of the startup timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StartupPhase {
+    /// Which phase this is
+    pub kind: StartupPhaseKind,
+    /// Duration of the phase in seconds
+    pub duration: f32,
+    /// Electrical speed at the end of the phase in Hz
+    pub end_speed_hz: f32,
+}
+
+/// Phase by phase estimate of a startup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StartupTimeline {
+    /// Align, double align, IPD or slow first cycle
+    pub initial: StartupPhase,
+    /// Open loop ramp
+    pub open_loop: StartupPhase,
+    /// Closed loop acceleration
+    pub closed_loop: StartupPhase,
+}
+
+impl StartupTimeline {
+    /// Returns the phases in the order they run.
+    pub fn phases(&self) -> [StartupPhase; 3] {
+        [self.initial, self.open_loop, self.closed_loop]
+    }
+
+    /// Returns the total time to reach the target speed in seconds.
+    pub fn total_duration(&self) -> f32 {
+        self.initial.duration + self.open_loop.duration + self.closed_loop.duration
+    }
+}
+
+/// Errors from estimating a startup timeline.
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum StartupTimelineError {
+    /// The handoff speed is chosen by the device and no assumption was given.
+    #[error("automatic handoff enabled; set an assumed handoff speed")]
+    UnknownHandoffSpeed,
+    /// The open loop ramp never reaches the handoff speed.
+    #[error("open loop ramp never reaches the handoff speed")]
+    NoOpenLoopAcceleration,
+    /// The slow first cycle frequency is 0 Hz, e.g. because MAX_SPEED is 0.
+    #[error("slow first cycle frequency is 0 Hz")]
+    ZeroFirstCycleFrequency,
+}
+
+/// Startup timeline estimator.
+///
+/// All values are in physical units so they can be set directly when no registers are at hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StartupEstimator {
+    /// Startup sequence
+    pub sequence: StartupSequence,
+    /// Closed loop acceleration in Hz/s, `None` for no limit
+    pub closed_loop_acceleration: Option<f32>,
+    /// Open loop acceleration coefficient A1 in Hz/s
+    pub open_loop_a1: f32,
+    /// Open loop acceleration coefficient A2 in Hz/s²
+    pub open_loop_a2: f32,
+    /// Maximum electrical speed in Hz
+    pub max_speed_hz: f32,
+    /// Handoff speed in Hz assumed when automatic handoff is enabled
+    pub auto_handoff_hz: Option<f32>,
+}
+
+impl StartupEstimator {
+    /// Creates an estimator from the startup and closed loop registers.
+    ///
+    /// Returns `None` if the startup sequence holds a reserved value.
+    pub fn from_registers(
+        motor_startup1: &MotorStartup1,
+        motor_startup2: &MotorStartup2,
+        closed_loop1: &ClosedLoop1,
+        closed_loop4: &ClosedLoop4,
+    ) -> Option<Self> {
+        Some(StartupEstimator {
+            sequence: StartupSequence::from_registers(motor_startup1, motor_startup2)?,
+            closed_loop_acceleration: closed_loop1.cl_acc().to_hz_per_second(),
+            open_loop_a1: motor_startup2.ol_acc_a1().to_hz_per_second(),
+            open_loop_a2: motor_startup2.ol_acc_a2().to_hz_per_second_squared(),
+            max_speed_hz: closed_loop4.max_speed_hz(),
+            auto_handoff_hz: None,
+        })
+    }
+
+    /// Sets the handoff speed assumed when automatic handoff is enabled.
+    pub fn with_auto_handoff_hz(mut self, hz: f32) -> Self {
+        self.auto_handoff_hz = Some(hz);
+        self
+    }
+
+    /// Returns the handoff speed in Hz.
+    pub fn handoff_hz(&self) -> Result<f32, StartupTimelineError> {
+        match self.sequence.handoff {
+            Handoff::Auto => self
+                .auto_handoff_hz
+                .ok_or(StartupTimelineError::UnknownHandoffSpeed),
+            Handoff::Threshold(threshold) => Ok(threshold.to_percent() / 100.0 * self.max_speed_hz),
+        }
+    }
+
+    /// Estimates the time to reach MAX_SPEED.
+    pub fn estimate_to_max_speed(&self) -> Result<StartupTimeline, StartupTimelineError> {
+        self.estimate(self.max_speed_hz)
+    }
+
+    /// Estimates the time to reach the given electrical speed in Hz.
+    ///
+    /// The IPD phase duration is an estimate, see the module documentation.
+    /// The target is limited to MAX_SPEED. A target below the handoff speed still runs the whole
+    /// open loop ramp, since the device always hands off before regulating speed.
+    pub fn estimate(&self, target_hz: f32) -> Result<StartupTimeline, StartupTimelineError> {
+        let handoff_hz = self.handoff_hz()?;
+        let target_hz = target_hz.min(self.max_speed_hz).max(handoff_hz);
+        let initial = self.initial_phase()?;
+
+        let open_loop = StartupPhase {
+            kind: StartupPhaseKind::OpenLoop,
+            duration: self.open_loop_time(initial.end_speed_hz, handoff_hz)?,
+            end_speed_hz: handoff_hz,
+        };
+        let closed_loop = StartupPhase {
+            kind: StartupPhaseKind::ClosedLoop,
+            duration: match self.closed_loop_acceleration {
+                Some(acceleration) => (target_hz - handoff_hz) / acceleration,
+                None => 0.0,
+            },
+            end_speed_hz: target_hz,
+        };
+        Ok(StartupTimeline {
+            initial,
+            open_loop,
+            closed_loop,
+        })
+    }
+
+    fn current_ramp_time(&self, current: CurrentSelection) -> f32 {
+        match self.sequence.ramp_rate.to_amps_per_second() {
+            Some(rate) => current.to_amps() / rate,
+            None => 0.0,
+        }
+    }
+
+    fn first_cycle_hz(&self, frequency: Option<SlowFirstCycleFrequency>) -> f32 {
+        frequency.map_or(0.0, |frequency| {
+            frequency.to_percent() / 100.0 * self.max_speed_hz
+        })
+    }
+
+    fn initial_phase(&self) -> Result<StartupPhase, StartupTimelineError> {
+        Ok(match self.sequence.method {
+            StartupMethod::Align(align) => StartupPhase {
+                kind: StartupPhaseKind::Align,
+                duration: self.current_ramp_time(align.current_limit)
+                    + align.align_time.to_seconds(),
+                end_speed_hz: self.first_cycle_hz(align.first_cycle_freq),
+            },
+            StartupMethod::DoubleAlign(align) => StartupPhase {
+                kind: StartupPhaseKind::DoubleAlign,
+                duration: 2.0
+                    * (self.current_ramp_time(align.current_limit) + align.align_time.to_seconds()),
+                end_speed_hz: self.first_cycle_hz(align.first_cycle_freq),
+            },
+            StartupMethod::Ipd(ipd) => StartupPhase {
+                kind: StartupPhaseKind::Ipd,
+                duration: (ipd.repeat.value() as f32 + 1.0) * IPD_CLOCKS_PER_RUN
+                    / ipd.clock_freq.to_hz(),
+                end_speed_hz: self.first_cycle_hz(ipd.first_cycle_freq),
+            },
+            StartupMethod::SlowFirstCycle(slow) => {
+                let frequency = self.first_cycle_hz(Some(slow.frequency));
+                if frequency <= 0.0 {
+                    return Err(StartupTimelineError::ZeroFirstCycleFrequency);
+                }
+                StartupPhase {
+                    kind: StartupPhaseKind::SlowFirstCycle,
+                    duration: self.current_ramp_time(slow.current_limit) + 1.0 / frequency,
+                    end_speed_hz: frequency,
+                }
+            }
+        })
+    }
+
+    /// Time for the open loop ramp `f = f0 + A1 * t + A2 * t² / 2` to reach `end_hz`.
+    fn open_loop_time(&self, start_hz: f32, end_hz: f32) -> Result<f32, StartupTimelineError> {
+        let delta = end_hz - start_hz;
+        if delta <= 0.0 {
+            return Ok(0.0);
+        }
+        let (a1, a2) = (self.open_loop_a1, self.open_loop_a2);
+        if a2 > 0.0 {
+            Ok((sqrt(a1 * a1 + 2.0 * a2 * delta) - a1) / a2)
+        } else if a1 > 0.0 {
+            Ok(delta / a1)
+        } else {
+            Err(StartupTimelineError::NoOpenLoopAcceleration)
+        }
+    }
+}
+
+/// Square root by Newton's method, since `f32::sqrt` is not available without `std`.
+fn sqrt(value: f32) -> f32 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let mut root = if value > 1.0 { value / 2.0 } else { 1.0 };
+    for _ in 0..64 {
+        let next = 0.5 * (root + value / root);
+        if (next - root).abs() <= f32::EPSILON * next {
+            return next;
+        }
+        root = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrary_int::u2;
+
+    const MAX_SPEED_HZ: f32 = 1000.0;
+
+    fn estimator(method: StartupMethod) -> StartupEstimator {
+        StartupEstimator {
+            sequence: StartupSequence {
+                method,
+                ramp_rate: CurrentRampRate::A10,
+                handoff: Handoff::Threshold(OpenCloseLoopHandoffThreshold::P20),
+            },
+            closed_loop_acceleration: Some(400.0),
+            open_loop_a1: 100.0,
+            open_loop_a2: 0.0,
+            max_speed_hz: MAX_SPEED_HZ,
+            auto_handoff_hz: None,
+        }
+    }
+
+    fn align() -> StartupMethod {
+        StartupMethod::Align(AlignStartup {
+            align_time: AlignTime::MS500,
+            current_limit: CurrentSelection::A1_0,
+            align_angle: AlignAngle::Deg0,
+            first_cycle_freq: Some(SlowFirstCycleFrequency::P10),
+        })
+    }
+
+    fn ipd() -> StartupMethod {
+        StartupMethod::Ipd(IpdStartup {
+            clock_freq: IpdClockFreq::Hz1000,
+            current_threshold: IpdCurrentThreshold::A0_5,
+            release_tristate: false,
+            advance_angle: IpdAdvanceAngle::Deg0,
+            repeat: u2::new(1),
+            first_cycle_freq: None,
+        })
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn align_ramps_current_then_holds() {
+        let timeline = estimator(align()).estimate_to_max_speed().unwrap();
+        assert_eq!(timeline.initial.kind, StartupPhaseKind::Align);
+        // 1 A at 10 A/s, then 500 ms of align time.
+        assert_close(timeline.initial.duration, 0.6);
+        assert_close(timeline.initial.end_speed_hz, 100.0);
+    }
+
+    #[test]
+    fn ipd_runs_repeat_plus_one_times() {
+        let timeline = estimator(ipd()).estimate_to_max_speed().unwrap();
+        assert_eq!(timeline.initial.kind, StartupPhaseKind::Ipd);
+        assert_close(timeline.initial.duration, 2.0 * IPD_CLOCKS_PER_RUN / 1000.0);
+        assert_close(timeline.initial.end_speed_hz, 0.0);
+    }
+
+    #[test]
+    fn open_loop_ramps_to_handoff() {
+        let mut estimator = estimator(align());
+        let timeline = estimator.estimate_to_max_speed().unwrap();
+        // From 100 Hz to the 200 Hz handoff at 100 Hz/s.
+        assert_close(timeline.open_loop.duration, 1.0);
+        assert_close(timeline.open_loop.end_speed_hz, 200.0);
+
+        // 100 Hz = 100 Hz/s * t + 200 Hz/s² * t² / 2
+        estimator.open_loop_a2 = 200.0;
+        let timeline = estimator.estimate_to_max_speed().unwrap();
+        assert_close(
+            timeline.open_loop.duration,
+            (sqrt(50_000.0) - 100.0) / 200.0,
+        );
+    }
+
+    #[test]
+    fn open_loop_without_acceleration_is_rejected() {
+        let mut estimator = estimator(align());
+        estimator.open_loop_a1 = 0.0;
+        assert_eq!(
+            estimator.estimate_to_max_speed(),
+            Err(StartupTimelineError::NoOpenLoopAcceleration)
+        );
+    }
+
+    #[test]
+    fn closed_loop_accelerates_to_target() {
+        let mut estimator = estimator(align());
+        let timeline = estimator.estimate_to_max_speed().unwrap();
+        assert_close(timeline.closed_loop.duration, 2.0);
+        assert_close(timeline.closed_loop.end_speed_hz, MAX_SPEED_HZ);
+        assert_close(timeline.total_duration(), 0.6 + 1.0 + 2.0);
+
+        let timeline = estimator.estimate(600.0).unwrap();
+        assert_close(timeline.closed_loop.duration, 1.0);
+        let timeline = estimator.estimate(2.0 * MAX_SPEED_HZ).unwrap();
+        assert_close(timeline.closed_loop.end_speed_hz, MAX_SPEED_HZ);
+
+        estimator.closed_loop_acceleration = None;
+        let timeline = estimator.estimate_to_max_speed().unwrap();
+        assert_close(timeline.closed_loop.duration, 0.0);
+    }
+
+    #[test]
+    fn auto_handoff_needs_an_assumed_speed() {
+        let mut estimator = estimator(align());
+        estimator.sequence.handoff = Handoff::Auto;
+        assert_eq!(
+            estimator.estimate_to_max_speed(),
+            Err(StartupTimelineError::UnknownHandoffSpeed)
+        );
+        let timeline = estimator
+            .with_auto_handoff_hz(300.0)
+            .estimate_to_max_speed()
+            .unwrap();
+        assert_close(timeline.open_loop.end_speed_hz, 300.0);
+    }
+
+    #[test]
+    fn zero_hz_slow_first_cycle_is_rejected() {
+        let mut estimator = estimator(StartupMethod::SlowFirstCycle(SlowFirstCycleStartup {
+            frequency: SlowFirstCycleFrequency::P10,
+            current_limit: CurrentSelection::A1_0,
+        }));
+        let timeline = estimator.estimate_to_max_speed().unwrap();
+        assert_eq!(timeline.initial.kind, StartupPhaseKind::SlowFirstCycle);
+        assert_close(timeline.initial.duration, 0.1 + 1.0 / 100.0);
+
+        estimator.max_speed_hz = 0.0;
+        assert_eq!(
+            estimator.estimate_to_max_speed(),
+            Err(StartupTimelineError::ZeroFirstCycleFrequency)
+        );
+    }
+}
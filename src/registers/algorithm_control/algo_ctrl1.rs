@@ -0,0 +1,70 @@
+//! Section 7.7.7.1
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Register to control the device
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct AlgoCtrl1 {
+    /// Write the configuration to EEPROM
+    #[bit(31, rw)]
+    pub eeprom_wrt: bool,
+    /// Read the default configuration from EEPROM
+    #[bit(30, rw)]
+    pub eeprom_read: bool,
+    /// Clear all faults
+    #[bit(29, rw)]
+    pub clr_flt: bool,
+    /// Clear fault retry count
+    #[bit(28, rw)]
+    pub clr_flt_retry_count: bool,
+    /// EEPROM write access key
+    #[bits(20..=27, rw)]
+    pub eeprom_write_access_key: u8,
+    /// 9-bit value (in °) used during align state for forced align
+    #[bits(11..=19, rw)]
+    pub forced_align_angle: u9,
+    /// Tickle the external watchdog over I2C.
+    /// Automatically resets to 0 after being set.
+    #[bit(10, rw)]
+    pub watchdog_tickle: bool,
+}
+
+impl Register for AlgoCtrl1 {
+    const ADDRESS: u12 = ALGO_CTRL1;
+    const SAFE_WHILE_RUNNING: bool = true;
+    // The action bits 31-28 and watchdog_tickle clear themselves.
+    const VERIFY_MASK: u32 = 0x0FFF_FBFF;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
+
+impl AlgoCtrl1 {
+    /// Returns `current` with the self-clearing action bits (`eeprom_wrt`, `eeprom_read`,
+    /// `clr_flt`, `clr_flt_retry_count` and `watchdog_tickle`) and the EEPROM write access key
+    /// cleared, keeping `forced_align_angle`.
+    ///
+    /// Writing the result back with a single action bit set triggers only that action.
+    pub fn without_actions(current: AlgoCtrl1) -> Self {
+        current
+            .with_eeprom_wrt(false)
+            .with_eeprom_read(false)
+            .with_clr_flt(false)
+            .with_clr_flt_retry_count(false)
+            .with_eeprom_write_access_key(0)
+            .with_watchdog_tickle(false)
+    }
+
+    /// Returns `current` with only `watchdog_tickle` set among the action bits.
+    pub fn tickle_from(current: AlgoCtrl1) -> Self {
+        Self::without_actions(current).with_watchdog_tickle(true)
+    }
+}
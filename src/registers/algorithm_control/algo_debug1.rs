@@ -0,0 +1,81 @@
+//! Section 7.7.7.2
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Register to override the speed input and force algorithm stages
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct AlgoDebug1 {
+    /// Use DIGITAL_SPEED_CTRL as the speed command.
+    /// 0 = Speed command from SPEED pin, 1 = Speed command from DIGITAL_SPEED_CTRL
+    #[bit(31, rw)]
+    pub override_speed: bool,
+    /// Digital speed command, DIGITAL_SPEED_CTRL / 32767 of the maximum speed
+    #[bits(16..=30, rw)]
+    pub digital_speed_ctrl: u15,
+    /// Disable closed loop.
+    /// 0 = Closed loop enabled, 1 = Motor stays in open loop
+    #[bit(15, rw)]
+    pub closed_loop_dis: bool,
+    /// Force align state.
+    /// 0 = Disable, 1 = Enable
+    #[bit(14, rw)]
+    pub force_align_en: bool,
+    /// Force slow first cycle state.
+    /// 0 = Disable, 1 = Enable
+    #[bit(13, rw)]
+    pub force_slow_first_cycle_en: bool,
+    /// Force IPD state.
+    /// 0 = Disable, 1 = Enable
+    #[bit(12, rw)]
+    pub force_ipd_en: bool,
+    /// Force ISD state.
+    /// 0 = Disable, 1 = Enable
+    #[bit(11, rw)]
+    pub force_isd_en: bool,
+    /// Source of the align angle.
+    /// 0 = ALIGN_ANGLE, 1 = FORCED_ALIGN_ANGLE
+    #[bit(10, rw)]
+    pub force_align_angle_src_sel: bool,
+    /// Iq reference when the speed loop is disabled
+    #[bits(0..=9, rw)]
+    pub force_iq_ref_speed_loop_dis: u10,
+}
+
+impl Register for AlgoDebug1 {
+    const ADDRESS: u12 = ALGO_DEBUG1;
+    const SAFE_WHILE_RUNNING: bool = true;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
+
+impl AlgoDebug1 {
+    /// Returns the digital speed command in percent of the maximum speed.
+    pub fn digital_speed_percent(&self) -> f32 {
+        self.digital_speed_ctrl().value() as f32 / u15::MAX.value() as f32 * 100.0
+    }
+
+    /// Sets the digital speed command in percent of the maximum speed.
+    /// Returns the encoded field along with the command actually configured.
+    pub fn set_digital_speed_percent(
+        &mut self,
+        percent: f32,
+    ) -> Result<Quantized<u15>, OutOfRangeError> {
+        OutOfRangeError::check(percent, 0.0, 100.0)?;
+        let value = u15::new((percent / 100.0 * u15::MAX.value() as f32 + 0.5) as u16);
+        self.set_digital_speed_ctrl(value);
+        Ok(Quantized {
+            value,
+            requested: percent,
+            actual: self.digital_speed_percent(),
+        })
+    }
+}
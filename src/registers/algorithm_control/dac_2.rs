@@ -0,0 +1,38 @@
+//! Section 7.7.7
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Register to configure DACOUT2
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct Dac2 {
+    /// 12-bit address of the variable to be monitored on DACOUT2
+    #[bits(19..=30, rw)]
+    pub dacout2_var_addr: u12,
+    /// DACOUT2 unscaled.
+    /// 0 = Output scaled by DACOUT2_SCALE, 1 = Output unscaled
+    #[bit(18, rw)]
+    pub dacout2_unscaled: bool,
+    /// Scaling factor applied to the monitored variable, in 1/2^12 steps
+    #[bits(2..=17, rw)]
+    pub dacout2_scale: u16,
+    /// DACOUT2 centered.
+    /// 0 = Output starts at 0 V, 1 = Output centered around mid-scale
+    #[bit(1, rw)]
+    pub dacout2_centered: bool,
+}
+
+impl Register for Dac2 {
+    const ADDRESS: u12 = DAC_2;
+    const SAFE_WHILE_RUNNING: bool = true;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
@@ -0,0 +1,287 @@
+//! Routing of RAM variables to DACOUT1 and DACOUT2 through `Dac1` and `Dac2`
+//!
+//! Section 7.7.7
+
+use core::fmt;
+
+use super::dac_1::Dac1;
+use super::dac_2::Dac2;
+use super::*;
+use arbitrary_int::*;
+
+/// RAM variable that can be driven onto a DAC output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DacVariable {
+    /// Calculated DC bus current
+    BusCurrent,
+    /// Measured current on phase A
+    PhaseCurrentA,
+    /// Measured current on phase B
+    PhaseCurrentB,
+    /// Measured current on phase C
+    PhaseCurrentC,
+    /// VM voltage
+    VmVoltage,
+    /// Phase A voltage
+    PhaseVoltageVa,
+    /// Phase B voltage
+    PhaseVoltageVb,
+    /// Phase C voltage
+    PhaseVoltageVc,
+    /// Alpha axis current
+    Ialpha,
+    /// Beta axis current
+    Ibeta,
+    /// Alpha axis voltage
+    Valpha,
+    /// Beta axis voltage
+    Vbeta,
+    /// Measured d-axis current
+    Id,
+    /// Measured q-axis current
+    Iq,
+    /// d-axis voltage
+    Vd,
+    /// q-axis voltage
+    Vq,
+    /// Open loop speed reference
+    SpeedRefOpenLoop,
+    /// Closed loop speed reference
+    SpeedRefClosedLoop,
+    /// Closed loop d-axis current reference
+    IdRefClosedLoop,
+    /// Closed loop q-axis current reference
+    IqRefClosedLoop,
+    /// Estimated d-axis BEMF
+    Ed,
+    /// Estimated q-axis BEMF
+    Eq,
+    /// Speed feedback
+    SpeedFdbk,
+    /// Estimated rotor position
+    ThetaEst,
+    /// Any other variable, by address
+    Address(u12),
+}
+
+impl DacVariable {
+    const NAMED: [DacVariable; 24] = [
+        DacVariable::BusCurrent,
+        DacVariable::PhaseCurrentA,
+        DacVariable::PhaseCurrentB,
+        DacVariable::PhaseCurrentC,
+        DacVariable::VmVoltage,
+        DacVariable::PhaseVoltageVa,
+        DacVariable::PhaseVoltageVb,
+        DacVariable::PhaseVoltageVc,
+        DacVariable::Ialpha,
+        DacVariable::Ibeta,
+        DacVariable::Valpha,
+        DacVariable::Vbeta,
+        DacVariable::Id,
+        DacVariable::Iq,
+        DacVariable::Vd,
+        DacVariable::Vq,
+        DacVariable::SpeedRefOpenLoop,
+        DacVariable::SpeedRefClosedLoop,
+        DacVariable::IdRefClosedLoop,
+        DacVariable::IqRefClosedLoop,
+        DacVariable::Ed,
+        DacVariable::Eq,
+        DacVariable::SpeedFdbk,
+        DacVariable::ThetaEst,
+    ];
+
+    /// Returns the address of the variable.
+    pub fn address(&self) -> u12 {
+        match self {
+            DacVariable::BusCurrent => BUS_CURRENT,
+            DacVariable::PhaseCurrentA => PHASE_CURRENT_A,
+            DacVariable::PhaseCurrentB => PHASE_CURRENT_B,
+            DacVariable::PhaseCurrentC => PHASE_CURRENT_C,
+            DacVariable::VmVoltage => VM_VOLTAGE,
+            DacVariable::PhaseVoltageVa => PHASE_VOLTAGE_VA,
+            DacVariable::PhaseVoltageVb => PHASE_VOLTAGE_VB,
+            DacVariable::PhaseVoltageVc => PHASE_VOLTAGE_VC,
+            DacVariable::Ialpha => IALPHA,
+            DacVariable::Ibeta => IBETA,
+            DacVariable::Valpha => VALPHA,
+            DacVariable::Vbeta => VBETA,
+            DacVariable::Id => ID,
+            DacVariable::Iq => IQ,
+            DacVariable::Vd => VD,
+            DacVariable::Vq => VQ,
+            DacVariable::SpeedRefOpenLoop => SPEED_REF_OPEN_LOOP,
+            DacVariable::SpeedRefClosedLoop => SPEED_REF_CLOSED_LOOP,
+            DacVariable::IdRefClosedLoop => ID_REF_CLOSED_LOOP,
+            DacVariable::IqRefClosedLoop => IQ_REF_CLOSED_LOOP,
+            DacVariable::Ed => ED,
+            DacVariable::Eq => EQ,
+            DacVariable::SpeedFdbk => SPEED_FDBK,
+            DacVariable::ThetaEst => THETA_EST,
+            DacVariable::Address(address) => *address,
+        }
+    }
+
+    /// Returns the named variable at `address`, or `DacVariable::Address` if it has no name.
+    pub fn from_address(address: u12) -> Self {
+        Self::NAMED
+            .into_iter()
+            .find(|variable| variable.address() == address)
+            .unwrap_or(DacVariable::Address(address))
+    }
+}
+
+impl fmt::Display for DacVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DacVariable::BusCurrent => f.write_str("BUS_CURRENT"),
+            DacVariable::PhaseCurrentA => f.write_str("PHASE_CURRENT_A"),
+            DacVariable::PhaseCurrentB => f.write_str("PHASE_CURRENT_B"),
+            DacVariable::PhaseCurrentC => f.write_str("PHASE_CURRENT_C"),
+            DacVariable::VmVoltage => f.write_str("VM_VOLTAGE"),
+            DacVariable::PhaseVoltageVa => f.write_str("PHASE_VOLTAGE_VA"),
+            DacVariable::PhaseVoltageVb => f.write_str("PHASE_VOLTAGE_VB"),
+            DacVariable::PhaseVoltageVc => f.write_str("PHASE_VOLTAGE_VC"),
+            DacVariable::Ialpha => f.write_str("IALPHA"),
+            DacVariable::Ibeta => f.write_str("IBETA"),
+            DacVariable::Valpha => f.write_str("VALPHA"),
+            DacVariable::Vbeta => f.write_str("VBETA"),
+            DacVariable::Id => f.write_str("ID"),
+            DacVariable::Iq => f.write_str("IQ"),
+            DacVariable::Vd => f.write_str("VD"),
+            DacVariable::Vq => f.write_str("VQ"),
+            DacVariable::SpeedRefOpenLoop => f.write_str("SPEED_REF_OPEN_LOOP"),
+            DacVariable::SpeedRefClosedLoop => f.write_str("SPEED_REF_CLOSED_LOOP"),
+            DacVariable::IdRefClosedLoop => f.write_str("ID_REF_CLOSED_LOOP"),
+            DacVariable::IqRefClosedLoop => f.write_str("IQ_REF_CLOSED_LOOP"),
+            DacVariable::Ed => f.write_str("ED"),
+            DacVariable::Eq => f.write_str("EQ"),
+            DacVariable::SpeedFdbk => f.write_str("SPEED_FDBK"),
+            DacVariable::ThetaEst => f.write_str("THETA_EST"),
+            DacVariable::Address(address) => write!(f, "{:#05X}", address.value()),
+        }
+    }
+}
+
+/// DAC output pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum DacChannel {
+    /// DACOUT1, configured by `Dac1`
+    #[strum(to_string = "DACOUT1")]
+    DacOut1,
+    /// DACOUT2, configured by `Dac2`
+    #[strum(to_string = "DACOUT2")]
+    DacOut2,
+}
+
+/// Offset of a DAC output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DacOffset {
+    /// Zero maps to 0 V; suited to unsigned variables.
+    Zero,
+    /// Zero maps to mid-scale; suited to signed variables.
+    MidScale,
+}
+
+/// Variable routed to a DAC output, with its scale and offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DacRoute {
+    /// Monitored variable
+    pub variable: DacVariable,
+    /// Scaling factor, or `None` for the unscaled variable
+    pub scale: Option<Quantized<u16>>,
+    /// Output offset
+    pub offset: DacOffset,
+}
+
+impl DacRoute {
+    /// Resolution of the scaling factor.
+    pub const SCALE_RESOLUTION: f32 = 1.0 / 4096.0;
+    /// Largest scaling factor.
+    pub const MAX_SCALE: f32 = u16::MAX as f32 / 4096.0;
+
+    /// Routes `variable` unscaled with no offset.
+    pub const fn new(variable: DacVariable) -> Self {
+        DacRoute {
+            variable,
+            scale: None,
+            offset: DacOffset::Zero,
+        }
+    }
+
+    /// Scales the variable by `scale`, rounded to the nearest 1/4096.
+    pub fn with_scale(mut self, scale: f32) -> Result<Self, OutOfRangeError> {
+        let scale = OutOfRangeError::check(scale, 0.0, Self::MAX_SCALE)?;
+        let value = (scale * 4096.0 + 0.5) as u16;
+        self.scale = Some(Quantized {
+            value,
+            requested: scale,
+            actual: value as f32 * Self::SCALE_RESOLUTION,
+        });
+        Ok(self)
+    }
+
+    /// Sets the output offset.
+    pub const fn with_offset(mut self, offset: DacOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn decode(address: u12, unscaled: bool, scale: u16, centered: bool) -> Self {
+        let actual = scale as f32 * Self::SCALE_RESOLUTION;
+        DacRoute {
+            variable: DacVariable::from_address(address),
+            scale: (!unscaled).then_some(Quantized {
+                value: scale,
+                requested: actual,
+                actual,
+            }),
+            offset: if centered {
+                DacOffset::MidScale
+            } else {
+                DacOffset::Zero
+            },
+        }
+    }
+
+    /// Reads the route out of `Dac1`.
+    pub fn from_dac1(dac1: &Dac1) -> Self {
+        Self::decode(
+            dac1.dacout1_var_addr(),
+            dac1.dacout1_unscaled(),
+            dac1.dacout1_scale(),
+            dac1.dacout1_centered(),
+        )
+    }
+
+    /// Reads the route out of `Dac2`.
+    pub fn from_dac2(dac2: &Dac2) -> Self {
+        Self::decode(
+            dac2.dacout2_var_addr(),
+            dac2.dacout2_unscaled(),
+            dac2.dacout2_scale(),
+            dac2.dacout2_centered(),
+        )
+    }
+
+    /// Writes the route into `Dac1`. The scale field is left untouched when unscaled.
+    pub fn apply_dac1(&self, dac1: &mut Dac1) {
+        dac1.set_dacout1_var_addr(self.variable.address());
+        dac1.set_dacout1_unscaled(self.scale.is_none());
+        if let Some(scale) = self.scale {
+            dac1.set_dacout1_scale(scale.value);
+        }
+        dac1.set_dacout1_centered(self.offset == DacOffset::MidScale);
+    }
+
+    /// Writes the route into `Dac2`. The scale field is left untouched when unscaled.
+    pub fn apply_dac2(&self, dac2: &mut Dac2) {
+        dac2.set_dacout2_var_addr(self.variable.address());
+        dac2.set_dacout2_unscaled(self.scale.is_none());
+        if let Some(scale) = self.scale {
+            dac2.set_dacout2_scale(scale.value);
+        }
+        dac2.set_dacout2_centered(self.offset == DacOffset::MidScale);
+    }
+}
@@ -0,0 +1,11 @@
+//! Section 7.7.7
+
+pub mod algo_ctrl1;
+pub mod algo_debug1;
+pub mod dac_1;
+pub mod dac_2;
+pub mod dac_routing;
+
+pub use super::addresses::*;
+pub use super::common::*;
+pub use super::register::Register;
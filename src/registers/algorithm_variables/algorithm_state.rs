@@ -0,0 +1,141 @@
+//! Section 7.7.8.1
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Current algorithm state
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct AlgorithmState {
+    /// Current algorithm state
+    #[bits(0..=15, r)]
+    pub algorithm_state: Option<MotorState>,
+}
+
+impl Register for AlgorithmState {
+    const ADDRESS: u12 = ALGORITHM_STATE;
+    const SAFE_WHILE_RUNNING: bool = true;
+    // Read-only, so nothing written reads back.
+    const VERIFY_MASK: u32 = 0;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
+
+/// Current algorithm state
+#[bitenum(u16, exhaustive = false)]
+#[derive(Debug, PartialEq, Eq, strum::Display)]
+pub enum MotorState {
+    /// Motor idle
+    #[strum(to_string = "MOTOR_IDLE")]
+    Idle = 0x0,
+    /// Initial speed detection
+    #[strum(to_string = "MOTOR_ISD")]
+    Isd = 0x1,
+    /// Motor coasting (Hi-Z)
+    #[strum(to_string = "MOTOR_TRISTATE")]
+    Tristate = 0x2,
+    /// Brake before start-up
+    #[strum(to_string = "MOTOR_BRAKE_ON_START")]
+    BrakeOnStart = 0x3,
+    /// Initial position detection
+    #[strum(to_string = "MOTOR_IPD")]
+    Ipd = 0x4,
+    /// Slow first cycle
+    #[strum(to_string = "MOTOR_SLOW_FIRST_CYCLE")]
+    SlowFirstCycle = 0x5,
+    /// Align
+    #[strum(to_string = "MOTOR_ALIGN")]
+    Align = 0x6,
+    /// Open loop
+    #[strum(to_string = "MOTOR_OPEN_LOOP")]
+    OpenLoop = 0x7,
+    /// Closed loop, estimator not yet aligned
+    #[strum(to_string = "MOTOR_CLOSED_LOOP_UNALIGNED")]
+    ClosedLoopUnaligned = 0x8,
+    /// Closed loop, estimator aligned
+    #[strum(to_string = "MOTOR_CLOSED_LOOP_ALIGNED")]
+    ClosedLoopAligned = 0x9,
+    /// Closed loop active braking
+    #[strum(to_string = "MOTOR_CLOSED_LOOP_ACTIVE_BRAKING")]
+    ClosedLoopActiveBraking = 0xA,
+    /// Soft stop
+    #[strum(to_string = "MOTOR_SOFT_STOP")]
+    SoftStop = 0xB,
+    /// Recirculation stop
+    #[strum(to_string = "MOTOR_RECIRCULATE_STOP")]
+    RecirculateStop = 0xC,
+    /// Brake on stop
+    #[strum(to_string = "MOTOR_BRAKE_ON_STOP")]
+    BrakeOnStop = 0xD,
+    /// Fault
+    #[strum(to_string = "MOTOR_FAULT")]
+    Fault = 0xE,
+    /// MPET motor stop check
+    #[strum(to_string = "MPET_MOTOR_STOP_CHECK")]
+    MpetMotorStopCheck = 0xF,
+    /// MPET motor stop wait
+    #[strum(to_string = "MPET_MOTOR_STOP_WAIT")]
+    MpetMotorStopWait = 0x10,
+    /// MPET motor brake
+    #[strum(to_string = "MPET_MOTOR_BRAKE")]
+    MpetMotorBrake = 0x11,
+    /// MPET algorithm parameters initialization
+    #[strum(to_string = "MPET_ALGORITHM_PARAMETERS_INIT")]
+    MpetAlgorithmParametersInit = 0x12,
+    /// MPET resistance and inductance measurement
+    #[strum(to_string = "MPET_RL_MEASURE")]
+    MpetRlMeasure = 0x13,
+    /// MPET BEMF constant measurement
+    #[strum(to_string = "MPET_KE_MEASURE")]
+    MpetKeMeasure = 0x14,
+    /// MPET stall current measurement
+    #[strum(to_string = "MPET_STALL_CURRENT_MEASURE")]
+    MpetStallCurrentMeasure = 0x15,
+    /// MPET torque mode speed check
+    #[strum(to_string = "MPET_TORQUE_MODE_SPEED_CHECK")]
+    MpetTorqueModeSpeedCheck = 0x16,
+    /// MPET done
+    #[strum(to_string = "MPET_DONE")]
+    MpetDone = 0x17,
+}
+
+impl MotorState {
+    /// Returns true in the closed loop states.
+    pub fn is_closed_loop(&self) -> bool {
+        matches!(
+            self,
+            MotorState::ClosedLoopUnaligned
+                | MotorState::ClosedLoopAligned
+                | MotorState::ClosedLoopActiveBraking
+        )
+    }
+
+    /// Returns true while the device is starting, driving, stopping or measuring the motor,
+    /// i.e. in every state except idle, fault and MPET done.
+    pub fn is_running(&self) -> bool {
+        !matches!(
+            self,
+            MotorState::Idle | MotorState::Fault | MotorState::MpetDone
+        )
+    }
+
+    /// Returns true in the states the device goes through while stopping the motor or
+    /// detecting its speed before a restart.
+    pub fn is_stopping(&self) -> bool {
+        matches!(
+            self,
+            MotorState::Isd
+                | MotorState::Tristate
+                | MotorState::SoftStop
+                | MotorState::RecirculateStop
+                | MotorState::BrakeOnStop
+        )
+    }
+}
@@ -0,0 +1,7 @@
+//! Section 7.7.8
+
+pub mod algorithm_state;
+
+pub use super::addresses::*;
+pub use super::common::*;
+pub use super::register::Register;
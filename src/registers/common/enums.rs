@@ -1,5 +1,6 @@
 //! Common enums used across various registers
 
+use super::{OutOfRangeError, Quantized};
 use bitbybit::bitenum;
 
 /// Current selection used in various registers
@@ -56,6 +57,37 @@ pub enum CurrentSelection {
     A8_0 = 0xF,
 }
 
+impl CurrentSelection {
+    const LEVELS: [(CurrentSelection, f32); 16] = [
+        (CurrentSelection::A0_125, 0.125),
+        (CurrentSelection::A0_25, 0.25),
+        (CurrentSelection::A0_5, 0.5),
+        (CurrentSelection::A1_0, 1.0),
+        (CurrentSelection::A1_5, 1.5),
+        (CurrentSelection::A2_0, 2.0),
+        (CurrentSelection::A2_5, 2.5),
+        (CurrentSelection::A3_0, 3.0),
+        (CurrentSelection::A3_5, 3.5),
+        (CurrentSelection::A4_0, 4.0),
+        (CurrentSelection::A4_5, 4.5),
+        (CurrentSelection::A5_0, 5.0),
+        (CurrentSelection::A5_5, 5.5),
+        (CurrentSelection::A6_0, 6.0),
+        (CurrentSelection::A7_0, 7.0),
+        (CurrentSelection::A8_0, 8.0),
+    ];
+
+    /// Returns the current in amps.
+    pub fn to_amps(&self) -> f32 {
+        Self::LEVELS[*self as usize].1
+    }
+
+    /// Returns the selection nearest to the given current in amps.
+    pub fn from_amps(amps: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        Quantized::snap(amps, Self::LEVELS)
+    }
+}
+
 /// Acceleration coefficient A1 options for open loop control
 #[bitenum(u4, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -110,6 +142,18 @@ pub enum OpenLoopAccelerationA1 {
     A10000 = 0xF,
 }
 
+impl OpenLoopAccelerationA1 {
+    const HZ_PER_SECOND: [f32; 16] = [
+        0.01, 0.05, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0,
+        5000.0, 10000.0,
+    ];
+
+    /// Returns the coefficient in Hz/s.
+    pub fn to_hz_per_second(&self) -> f32 {
+        Self::HZ_PER_SECOND[*self as usize]
+    }
+}
+
 /// Acceleration coefficient A2 options for open loop control
 #[bitenum(u4, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -163,3 +207,15 @@ pub enum OpenLoopAccelerationA2 {
     #[strum(to_string = "10000 Hz/s²")]
     A10000 = 0xF,
 }
+
+impl OpenLoopAccelerationA2 {
+    const HZ_PER_SECOND_SQUARED: [f32; 16] = [
+        0.0, 0.05, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0,
+        5000.0, 10000.0,
+    ];
+
+    /// Returns the coefficient in Hz/s².
+    pub fn to_hz_per_second_squared(&self) -> f32 {
+        Self::HZ_PER_SECOND_SQUARED[*self as usize]
+    }
+}
@@ -3,7 +3,9 @@
 mod enums;
 mod lock_mode;
 mod percent_as_u8;
+mod quantized;
 
 pub use enums::*;
 pub use lock_mode::*;
 pub use percent_as_u8::*;
+pub use quantized::*;
@@ -0,0 +1,80 @@
+use thiserror::Error;
+
+/// A physical value snapped to the nearest value a register field can represent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantized<T> {
+    /// The encoded register value.
+    pub value: T,
+    /// The physical value that was requested.
+    pub requested: f32,
+    /// The physical value actually represented by `value`.
+    pub actual: f32,
+}
+
+impl<T> Quantized<T> {
+    /// Quantization error, `actual - requested`, in the unit of the requested value.
+    pub fn error(&self) -> f32 {
+        self.actual - self.requested
+    }
+
+    /// Returns true if the requested value is represented exactly.
+    pub fn is_exact(&self) -> bool {
+        self.actual == self.requested
+    }
+}
+
+impl<T: Copy> Quantized<T> {
+    /// Picks the candidate whose physical value is nearest to `requested`. Ties go to the first
+    /// candidate.
+    ///
+    /// `requested` must lie between the smallest and largest candidate values, so a table never
+    /// silently saturates at one of its ends.
+    pub fn snap(
+        requested: f32,
+        candidates: impl IntoIterator<Item = (T, f32)>,
+    ) -> Result<Self, OutOfRangeError> {
+        let mut best: Option<Self> = None;
+        let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+        for (value, actual) in candidates {
+            min = min.min(actual);
+            max = max.max(actual);
+            if best.is_none_or(|best| (actual - requested).abs() < best.error().abs()) {
+                best = Some(Quantized {
+                    value,
+                    requested,
+                    actual,
+                });
+            }
+        }
+        OutOfRangeError::check(requested, min, max)?;
+        best.ok_or(OutOfRangeError {
+            value: requested,
+            min,
+            max,
+        })
+    }
+}
+
+/// Error returned when a physical value lies outside of the range a register field can represent.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+#[error("{value} is outside of the representable range {min} to {max}")]
+pub struct OutOfRangeError {
+    /// The requested value.
+    pub value: f32,
+    /// Smallest representable value.
+    pub min: f32,
+    /// Largest representable value.
+    pub max: f32,
+}
+
+impl OutOfRangeError {
+    /// Returns `Ok(value)` if `value` lies within `min..=max`, otherwise an `OutOfRangeError`.
+    /// NaN is always out of range.
+    pub fn check(value: f32, min: f32, max: f32) -> Result<f32, Self> {
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(OutOfRangeError { value, min, max })
+        }
+    }
+}
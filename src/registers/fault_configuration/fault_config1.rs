@@ -55,6 +55,38 @@ impl Register for FaultConfig1 {
     }
 }
 
+impl FaultConfig1 {
+    /// Lock current limit mode as a `LockMode`
+    pub fn lock_ilimit_mode_typed(&self) -> LockMode {
+        self.lock_ilimit_mode().into()
+    }
+
+    /// Sets the lock current limit mode from a `LockMode`
+    pub fn set_lock_ilimit_mode_typed(&mut self, mode: LockMode) {
+        self.set_lock_ilimit_mode(mode.into());
+    }
+
+    /// Returns a copy with the lock current limit mode set from a `LockMode`
+    pub fn with_lock_ilimit_mode_typed(&self, mode: LockMode) -> Self {
+        self.with_lock_ilimit_mode(mode.into())
+    }
+
+    /// Motor lock mode as a `LockMode`
+    pub fn mtr_lck_mode_typed(&self) -> LockMode {
+        self.mtr_lck_mode().into()
+    }
+
+    /// Sets the motor lock mode from a `LockMode`
+    pub fn set_mtr_lck_mode_typed(&mut self, mode: LockMode) {
+        self.set_mtr_lck_mode(mode.into());
+    }
+
+    /// Returns a copy with the motor lock mode set from a `LockMode`
+    pub fn with_mtr_lck_mode_typed(&self, mode: LockMode) -> Self {
+        self.with_mtr_lck_mode(mode.into())
+    }
+}
+
 /// Lock detection current limit deglitch time
 #[bitenum(u4, exhaustive = false)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -68,6 +68,23 @@ impl Register for FaultConfig2 {
     }
 }
 
+impl FaultConfig2 {
+    /// Hardware lock detection current mode as a `LockMode`
+    pub fn hw_lock_ilimit_mode_typed(&self) -> LockMode {
+        self.hw_lock_ilimit_mode().into()
+    }
+
+    /// Sets the hardware lock detection current mode from a `LockMode`
+    pub fn set_hw_lock_ilimit_mode_typed(&mut self, mode: LockMode) {
+        self.set_hw_lock_ilimit_mode(mode.into());
+    }
+
+    /// Returns a copy with the hardware lock detection current mode set from a `LockMode`
+    pub fn with_hw_lock_ilimit_mode_typed(&self, mode: LockMode) -> Self {
+        self.with_hw_lock_ilimit_mode(mode.into())
+    }
+}
+
 /// Abnormal speed lock threshold (% of MAX_SPEED)
 #[bitenum(u3, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -218,6 +235,31 @@ pub enum MinimumBusVoltage {
     V12_5 = 0x7,
 }
 
+impl MinimumBusVoltage {
+    const LEVELS: [(MinimumBusVoltage, f32); 7] = [
+        (MinimumBusVoltage::V4_5, 4.5),
+        (MinimumBusVoltage::V5, 5.0),
+        (MinimumBusVoltage::V5_5, 5.5),
+        (MinimumBusVoltage::V6, 6.0),
+        (MinimumBusVoltage::V7_5, 7.5),
+        (MinimumBusVoltage::V10, 10.0),
+        (MinimumBusVoltage::V12_5, 12.5),
+    ];
+
+    /// Returns the threshold in volts, or `None` for `NoLimit`.
+    pub fn to_volts(&self) -> Option<f32> {
+        Self::LEVELS
+            .iter()
+            .find(|(level, _)| level == self)
+            .map(|(_, volts)| *volts)
+    }
+
+    /// Returns the threshold nearest to the given voltage. `NoLimit` is never selected.
+    pub fn from_volts(volts: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        Quantized::snap(volts, Self::LEVELS)
+    }
+}
+
 /// Maximum DC Bus voltage for running motor
 #[bitenum(u3, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, strum::Display)]
@@ -265,6 +307,31 @@ impl Ord for MaximumBusVoltage {
     }
 }
 
+impl MaximumBusVoltage {
+    const LEVELS: [(MaximumBusVoltage, f32); 7] = [
+        (MaximumBusVoltage::V20, 20.0),
+        (MaximumBusVoltage::V22_5, 22.5),
+        (MaximumBusVoltage::V25, 25.0),
+        (MaximumBusVoltage::V27_5, 27.5),
+        (MaximumBusVoltage::V30, 30.0),
+        (MaximumBusVoltage::V32_5, 32.5),
+        (MaximumBusVoltage::V35, 35.0),
+    ];
+
+    /// Returns the threshold in volts, or `None` for `NoLimit`.
+    pub fn to_volts(&self) -> Option<f32> {
+        Self::LEVELS
+            .iter()
+            .find(|(level, _)| level == self)
+            .map(|(_, volts)| *volts)
+    }
+
+    /// Returns the threshold nearest to the given voltage. `NoLimit` is never selected.
+    pub fn from_volts(volts: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        Quantized::snap(volts, Self::LEVELS)
+    }
+}
+
 /// Automatic retry attempts
 #[bitenum(u3, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, strum::Display)]
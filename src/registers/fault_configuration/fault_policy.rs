@@ -0,0 +1,102 @@
+//! Lock fault policy, spread across `FaultConfig1` and `FaultConfig2`
+//!
+//! Section 7.7.2
+
+use super::fault_config1::{FaultConfig1, LockRetryTime};
+use super::fault_config2::{AutoRetryTimes, FaultConfig2};
+use super::*;
+
+/// Reaction of the device to lock conditions, configured as a single policy.
+///
+/// `auto_retry_times` and `lock_retry_time` are shared by every lock mode with `auto_retry` set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultPolicy {
+    /// ADC based lock detection current limit mode (`FaultConfig1::lock_ilimit_mode`)
+    pub lock_ilimit_mode: LockMode,
+    /// Motor lock mode (`FaultConfig1::mtr_lck_mode`)
+    pub mtr_lck_mode: LockMode,
+    /// Comparator based lock detection current mode (`FaultConfig2::hw_lock_ilimit_mode`)
+    pub hw_lock_ilimit_mode: LockMode,
+    /// Automatic retry attempts (`FaultConfig2::auto_retry_times`)
+    pub auto_retry_times: AutoRetryTimes,
+    /// Lock detection retry time (`FaultConfig1::lck_retry`)
+    pub lck_retry: LockRetryTime,
+}
+
+impl FaultPolicy {
+    /// Creates a policy applying the same mode to all three lock detections.
+    pub const fn new(
+        mode: LockMode,
+        auto_retry_times: AutoRetryTimes,
+        lck_retry: LockRetryTime,
+    ) -> Self {
+        FaultPolicy {
+            lock_ilimit_mode: mode,
+            mtr_lck_mode: mode,
+            hw_lock_ilimit_mode: mode,
+            auto_retry_times,
+            lck_retry,
+        }
+    }
+
+    /// Sets the ADC based lock detection current limit mode.
+    pub const fn with_lock_ilimit_mode(mut self, mode: LockMode) -> Self {
+        self.lock_ilimit_mode = mode;
+        self
+    }
+
+    /// Sets the motor lock mode.
+    pub const fn with_mtr_lck_mode(mut self, mode: LockMode) -> Self {
+        self.mtr_lck_mode = mode;
+        self
+    }
+
+    /// Sets the comparator based lock detection current mode.
+    pub const fn with_hw_lock_ilimit_mode(mut self, mode: LockMode) -> Self {
+        self.hw_lock_ilimit_mode = mode;
+        self
+    }
+
+    /// Sets the number of automatic retry attempts.
+    pub const fn with_auto_retry_times(mut self, auto_retry_times: AutoRetryTimes) -> Self {
+        self.auto_retry_times = auto_retry_times;
+        self
+    }
+
+    /// Sets the lock detection retry time.
+    pub const fn with_lck_retry(mut self, lck_retry: LockRetryTime) -> Self {
+        self.lck_retry = lck_retry;
+        self
+    }
+
+    /// Returns true if any of the lock modes automatically retries, meaning `auto_retry_times`
+    /// and `lck_retry` have an effect.
+    pub fn uses_auto_retry(&self) -> bool {
+        let retries =
+            |mode: LockMode| matches!(mode, LockMode::Enable { auto_retry, .. } if auto_retry);
+        retries(self.lock_ilimit_mode)
+            || retries(self.mtr_lck_mode)
+            || retries(self.hw_lock_ilimit_mode)
+    }
+
+    /// Reads the policy out of the two fault configuration registers.
+    pub fn from_registers(fault_config1: &FaultConfig1, fault_config2: &FaultConfig2) -> Self {
+        FaultPolicy {
+            lock_ilimit_mode: fault_config1.lock_ilimit_mode_typed(),
+            mtr_lck_mode: fault_config1.mtr_lck_mode_typed(),
+            hw_lock_ilimit_mode: fault_config2.hw_lock_ilimit_mode_typed(),
+            auto_retry_times: fault_config2.auto_retry_times(),
+            lck_retry: fault_config1.lck_retry(),
+        }
+    }
+
+    /// Writes the policy into the two fault configuration registers, leaving all other fields
+    /// untouched.
+    pub fn apply(&self, fault_config1: &mut FaultConfig1, fault_config2: &mut FaultConfig2) {
+        fault_config1.set_lock_ilimit_mode_typed(self.lock_ilimit_mode);
+        fault_config1.set_mtr_lck_mode_typed(self.mtr_lck_mode);
+        fault_config1.set_lck_retry(self.lck_retry);
+        fault_config2.set_hw_lock_ilimit_mode_typed(self.hw_lock_ilimit_mode);
+        fault_config2.set_auto_retry_times(self.auto_retry_times);
+    }
+}
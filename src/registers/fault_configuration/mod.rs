@@ -2,6 +2,7 @@
 
 pub mod fault_config1;
 pub mod fault_config2;
+pub mod fault_policy;
 
 pub use super::addresses::*;
 pub use super::common::*;
@@ -0,0 +1,80 @@
+//! Section 7.7.5.2
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Status of various controller faults
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct ControllerFaultStatus {
+    /// Logic OR of controller fault status bits
+    #[bit(31, r)]
+    pub controller_fault: bool,
+    /// Indicates IPD frequency fault
+    #[bit(29, r)]
+    pub ipd_freq_fault: bool,
+    /// Indicates IPD T1 fault
+    #[bit(28, r)]
+    pub ipd_t1_fault: bool,
+    /// Indicates IPD T2 fault
+    #[bit(27, r)]
+    pub ipd_t2_fault: bool,
+    /// Indicates status of bus current limit
+    #[bit(26, r)]
+    pub bus_current_limit_status: bool,
+    /// Indicates IPD fault during MPET
+    #[bit(25, r)]
+    pub mpet_ipd_fault: bool,
+    /// Indicates BEMF fault during MPET
+    #[bit(24, r)]
+    pub mpet_bemf_fault: bool,
+    /// Indicates abnormal speed motor lock condition
+    #[bit(23, r)]
+    pub abn_speed: bool,
+    /// Indicates abnormal BEMF motor lock condition
+    #[bit(22, r)]
+    pub abn_bemf: bool,
+    /// Indicates no motor fault
+    #[bit(21, r)]
+    pub no_mtr: bool,
+    /// Indicates motor lock
+    #[bit(20, r)]
+    pub mtr_lck: bool,
+    /// Indicates lock ILIMIT fault
+    #[bit(19, r)]
+    pub lock_ilimit: bool,
+    /// Indicates hardware lock ILIMIT fault
+    #[bit(18, r)]
+    pub hw_lock_ilimit: bool,
+    /// Indicates motor undervoltage fault
+    #[bit(17, r)]
+    pub mtr_under_voltage: bool,
+    /// Indicates motor overvoltage fault
+    #[bit(16, r)]
+    pub mtr_over_voltage: bool,
+    /// Indicates speed loop saturation
+    #[bit(15, r)]
+    pub speed_loop_saturation: bool,
+    /// Indicates current loop saturation
+    #[bit(14, r)]
+    pub current_loop_saturation: bool,
+    /// Indicates external watchdog fault
+    #[bit(2, r)]
+    pub watchdog_fault: bool,
+}
+
+impl Register for ControllerFaultStatus {
+    const ADDRESS: u12 = CONTROLLER_FAULT_STATUS;
+    const SAFE_WHILE_RUNNING: bool = true;
+    // Read-only, so nothing written reads back.
+    const VERIFY_MASK: u32 = 0;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
@@ -0,0 +1,80 @@
+//! Section 7.7.5.1
+
+use super::*;
+use arbitrary_int::*;
+use bitbybit::*;
+
+/// Status of various gate driver faults
+#[bitfield(u32, debug, default = 0x0)]
+#[derive(PartialEq, Eq)]
+pub struct GateDriverFaultStatus {
+    /// Logic OR of gate driver fault status bits
+    #[bit(31, r)]
+    pub driver_fault: bool,
+    /// Indicates buck fault
+    #[bit(30, r)]
+    pub bk_flt: bool,
+    /// Indicates overcurrent fault
+    #[bit(28, r)]
+    pub ocp: bool,
+    /// Indicates supply power on reset
+    #[bit(27, r)]
+    pub npor: bool,
+    /// Indicates supply overvoltage fault
+    #[bit(26, r)]
+    pub ovp: bool,
+    /// Indicates overtemperature fault
+    #[bit(25, r)]
+    pub ot: bool,
+    /// Indicates overtemperature warning
+    #[bit(20, r)]
+    pub otw: bool,
+    /// Indicates overtemperature shutdown
+    #[bit(19, r)]
+    pub tsd: bool,
+    /// Indicates overcurrent on high-side switch of OUTC
+    #[bit(18, r)]
+    pub ocp_hc: bool,
+    /// Indicates overcurrent on low-side switch of OUTC
+    #[bit(17, r)]
+    pub ocp_lc: bool,
+    /// Indicates overcurrent on high-side switch of OUTB
+    #[bit(16, r)]
+    pub ocp_hb: bool,
+    /// Indicates overcurrent on low-side switch of OUTB
+    #[bit(15, r)]
+    pub ocp_lb: bool,
+    /// Indicates overcurrent on high-side switch of OUTA
+    #[bit(14, r)]
+    pub ocp_ha: bool,
+    /// Indicates overcurrent on low-side switch of OUTA
+    #[bit(13, r)]
+    pub ocp_la: bool,
+    /// Indicates OTP read error
+    #[bit(11, r)]
+    pub otp_err: bool,
+    /// Indicates buck regulator overcurrent
+    #[bit(10, r)]
+    pub buck_ocp: bool,
+    /// Indicates buck regulator undervoltage
+    #[bit(9, r)]
+    pub buck_uv: bool,
+    /// Indicates charge pump undervoltage
+    #[bit(8, r)]
+    pub vcp_uv: bool,
+}
+
+impl Register for GateDriverFaultStatus {
+    const ADDRESS: u12 = GATE_DRIVER_FAULT_STATUS;
+    const SAFE_WHILE_RUNNING: bool = true;
+    // Read-only, so nothing written reads back.
+    const VERIFY_MASK: u32 = 0;
+
+    fn value(&self) -> u32 {
+        self.raw_value()
+    }
+
+    fn from_value(value: u32) -> Self {
+        Self::new_with_raw_value(value)
+    }
+}
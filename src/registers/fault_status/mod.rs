@@ -0,0 +1,8 @@
+//! Section 7.7.5
+
+pub mod controller_fault_status;
+pub mod gate_driver_fault_status;
+
+pub use super::addresses::*;
+pub use super::common::*;
+pub use super::register::Register;
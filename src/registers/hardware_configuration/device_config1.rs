@@ -128,3 +128,27 @@ impl PartialOrd for MaxBusVoltage {
         }
     }
 }
+
+impl MaxBusVoltage {
+    /// Returns the maximum DC bus voltage in volts, or `None` for `NotDefined`.
+    pub fn to_volts(&self) -> Option<f32> {
+        match self {
+            MaxBusVoltage::V15 => Some(15.0),
+            MaxBusVoltage::V30 => Some(30.0),
+            MaxBusVoltage::V60 => Some(60.0),
+            MaxBusVoltage::NotDefined => None,
+        }
+    }
+
+    /// Returns the smallest setting that covers the given bus voltage.
+    pub fn covering(volts: f32) -> Result<Self, OutOfRangeError> {
+        OutOfRangeError::check(volts, 0.0, 60.0)?;
+        Ok(if volts <= 15.0 {
+            MaxBusVoltage::V15
+        } else if volts <= 30.0 {
+            MaxBusVoltage::V30
+        } else {
+            MaxBusVoltage::V60
+        })
+    }
+}
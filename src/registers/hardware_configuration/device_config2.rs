@@ -68,6 +68,38 @@ impl Register for DeviceConfig2 {
     }
 }
 
+impl DeviceConfig2 {
+    /// Returns the low power mode entered when the speed command goes to zero.
+    pub fn low_power_mode(&self) -> LowPowerMode {
+        if self.dev_mode() {
+            LowPowerMode::Sleep
+        } else {
+            LowPowerMode::Standby
+        }
+    }
+
+    /// Sets the low power mode entered when the speed command goes to zero.
+    pub fn set_low_power_mode(&mut self, mode: LowPowerMode) {
+        self.set_dev_mode(mode == LowPowerMode::Sleep);
+    }
+
+    /// Returns a copy with the low power mode set.
+    pub fn with_low_power_mode(self, mode: LowPowerMode) -> Self {
+        self.with_dev_mode(mode == LowPowerMode::Sleep)
+    }
+}
+
+/// Low power mode entered when the speed command goes to zero (`DeviceConfig2::dev_mode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum LowPowerMode {
+    /// Standby; I2C stays available
+    #[strum(to_string = "Standby Mode")]
+    Standby,
+    /// Sleep; the device must be woken through the SPEED pin
+    #[strum(to_string = "Sleep Mode")]
+    Sleep,
+}
+
 /// Device enters sleep mode when input source is held at or below the
 /// sleep entry threshold for SLEEP_ENTRY_TIME
 #[bitenum(u2, exhaustive = true)]
@@ -133,6 +165,29 @@ pub enum ExternalClockFrequency {
     KHz1024 = 0x7,
 }
 
+impl ExternalClockFrequency {
+    /// Returns the frequency in Hz.
+    pub fn to_hz(&self) -> u32 {
+        8_000 << (*self as u32)
+    }
+
+    /// Returns the setting for an external clock of exactly `hz`, or `None` if the frequency is
+    /// not supported.
+    pub fn from_hz(hz: u32) -> Option<Self> {
+        match hz {
+            8_000 => Some(ExternalClockFrequency::KHz8),
+            16_000 => Some(ExternalClockFrequency::KHz16),
+            32_000 => Some(ExternalClockFrequency::KHz32),
+            64_000 => Some(ExternalClockFrequency::KHz64),
+            128_000 => Some(ExternalClockFrequency::KHz128),
+            256_000 => Some(ExternalClockFrequency::KHz256),
+            512_000 => Some(ExternalClockFrequency::KHz512),
+            1_024_000 => Some(ExternalClockFrequency::KHz1024),
+            _ => None,
+        }
+    }
+}
+
 /// Time between watchdog tickles (GPIO/I2C)
 #[bitenum(u2, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -150,3 +205,17 @@ pub enum ExternalWatchdogConfig {
     #[strum(to_string = "1000ms/10s")]
     X10 = 0x3,
 }
+
+impl ExternalWatchdogConfig {
+    /// Returns the time between watchdog tickles in milliseconds.
+    /// Tickles over I2C are allowed ten times as long as tickles over GPIO.
+    pub fn timeout_ms(&self, over_i2c: bool) -> u32 {
+        let gpio_ms = match self {
+            ExternalWatchdogConfig::X1 => 100,
+            ExternalWatchdogConfig::X2 => 200,
+            ExternalWatchdogConfig::X5 => 500,
+            ExternalWatchdogConfig::X10 => 1000,
+        };
+        if over_i2c { gpio_ms * 10 } else { gpio_ms }
+    }
+}
@@ -50,6 +50,7 @@ pub struct GdConfig1 {
 
 impl Register for GdConfig1 {
     const ADDRESS: u12 = GD_CONFIG1;
+    const VERIFY_MASK: u32 = 0x7FFF_FFFF;
 
     fn value(&self) -> u32 {
         let mut value = self.raw_value();
@@ -65,6 +66,49 @@ impl Register for GdConfig1 {
     }
 }
 
+impl GdConfig1 {
+    const OCP_LEVELS: [(bool, f32); 2] = [(false, 16.0), (true, 24.0)];
+
+    /// Returns the typical overcurrent protection level in amps.
+    pub fn ocp_level_amps(&self) -> f32 {
+        Self::OCP_LEVELS[self.ocp_lvl() as usize].1
+    }
+
+    /// Selects the overcurrent protection level (16 A or 24 A) nearest to the given current.
+    /// Returns the `ocp_lvl` value that was chosen along with its level.
+    pub fn set_ocp_level_amps(&mut self, amps: f32) -> Result<Quantized<bool>, OutOfRangeError> {
+        let level = Quantized::snap(amps, Self::OCP_LEVELS)?;
+        self.set_ocp_lvl(level.value);
+        Ok(level)
+    }
+
+    /// Returns the hardware overvoltage protection level in volts, or `None` if it is disabled.
+    pub fn ovp_level_volts(&self) -> Option<f32> {
+        match (self.ovp_en(), self.ovp_sel()) {
+            (false, _) => None,
+            (true, false) => Some(34.0),
+            (true, true) => Some(22.0),
+        }
+    }
+
+    /// Enables hardware overvoltage protection at the level (22 V or 34 V) nearest to the given
+    /// voltage, or disables it when `volts` is `None`.
+    /// Returns the `ovp_sel` value that was chosen along with its level.
+    pub fn set_ovp_level_volts(
+        &mut self,
+        volts: Option<f32>,
+    ) -> Result<Option<Quantized<bool>>, OutOfRangeError> {
+        let Some(volts) = volts else {
+            self.set_ovp_en(false);
+            return Ok(None);
+        };
+        let level = Quantized::snap(volts, [(false, 34.0), (true, 22.0)])?;
+        self.set_ovp_sel(level.value);
+        self.set_ovp_en(true);
+        Ok(Some(level))
+    }
+}
+
 /// Slew rate
 #[bitenum(u2, exhaustive = false)]
 #[derive(Debug, PartialEq, Eq, strum::Display)]
@@ -77,6 +121,20 @@ pub enum SlewRate {
     V200 = 0x3,
 }
 
+impl SlewRate {
+    const LEVELS: [(SlewRate, f32); 2] = [(SlewRate::V125, 125.0), (SlewRate::V200, 200.0)];
+
+    /// Returns the slew rate in V/µs.
+    pub fn to_volts_per_us(self) -> f32 {
+        Self::LEVELS[self as usize - SlewRate::V125 as usize].1
+    }
+
+    /// Returns the slew rate nearest to the given rate in V/µs.
+    pub fn from_volts_per_us(volts_per_us: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        Quantized::snap(volts_per_us, Self::LEVELS)
+    }
+}
+
 /// OCP Deglitch Time Settings
 #[bitenum(u2, exhaustive = true)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
@@ -126,3 +184,21 @@ pub enum CurrentSenseAmplifierGain {
     #[strum(to_string = "1.2 V/A")]
     VA1_2 = 0x3,
 }
+
+impl CurrentSenseAmplifierGain {
+    /// Returns the gain in V/A.
+    pub fn to_volts_per_amp(self) -> f32 {
+        match self {
+            CurrentSenseAmplifierGain::VA0_15 => 0.15,
+            CurrentSenseAmplifierGain::VA0_3 => 0.3,
+            CurrentSenseAmplifierGain::VA0_6 => 0.6,
+            CurrentSenseAmplifierGain::VA1_2 => 1.2,
+        }
+    }
+
+    /// Returns the largest phase current in amps that can be measured with this gain,
+    /// assuming the CSA output swings ±1.5 V around its mid-scale bias.
+    pub fn full_scale_current(self) -> f32 {
+        1.5 / self.to_volts_per_amp()
+    }
+}
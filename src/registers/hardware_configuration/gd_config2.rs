@@ -38,6 +38,7 @@ pub struct GdConfig2 {
 
 impl Register for GdConfig2 {
     const ADDRESS: u12 = GD_CONFIG2;
+    const VERIFY_MASK: u32 = 0x7EFF_FFFF;
 
     fn value(&self) -> u32 {
         let mut value = self.raw_value();
@@ -95,6 +96,20 @@ impl BuckVoltage {
             BuckVoltage::V5_7 => 5.7,
         }
     }
+
+    /// Returns the buck voltage nearest to the given voltage.
+    pub fn from_voltage(volts: f32) -> Result<Quantized<Self>, OutOfRangeError> {
+        Quantized::snap(
+            volts,
+            [
+                BuckVoltage::V3_3,
+                BuckVoltage::V4_0,
+                BuckVoltage::V5_0,
+                BuckVoltage::V5_7,
+            ]
+            .map(|buck| (buck, buck.to_voltage())),
+        )
+    }
 }
 
 /// Minimum ON time for low side MOSFET
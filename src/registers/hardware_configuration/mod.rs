@@ -6,6 +6,7 @@ pub mod gd_config1;
 pub mod gd_config2;
 pub mod peri_config1;
 pub mod pin_config;
+pub mod power_stage;
 
 pub use super::addresses::*;
 pub use super::common::*;
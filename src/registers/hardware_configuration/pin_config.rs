@@ -41,6 +41,7 @@ pub struct PinConfig {
 
 impl Register for PinConfig {
     const ADDRESS: u12 = PIN_CONFIG;
+    const SAFE_WHILE_RUNNING: bool = true;
 
     fn value(&self) -> u32 {
         self.raw_value()
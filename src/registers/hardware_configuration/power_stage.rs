@@ -0,0 +1,151 @@
+//! Gate driver and buck regulator, spread across `GdConfig1` and `GdConfig2`
+//!
+//! Section 7.7.3.5 and 7.7.3.6
+
+use core::fmt;
+
+use super::gd_config1::{
+    CurrentSenseAmplifierGain, GdConfig1, OvercurrentDeglitch, OvercurrentMode, SlewRate,
+};
+use super::gd_config2::{BuckVoltage, GdConfig2, MinOnTime};
+use super::*;
+
+/// Buck regulator current limit class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuckCurrentLimit {
+    /// 600 mA
+    Ma600,
+    /// 150 mA
+    Ma150,
+}
+
+/// Gate driver and buck regulator configuration in physical units.
+///
+/// `GdConfig2::buck_ps_dis` is left untouched; the parity bit of both registers keeps being
+/// computed by [`Register::value`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerStage {
+    /// Gate driver slew rate in V/µs (125 or 200)
+    pub slew_rate: f32,
+    /// Overcurrent protection level in amps (16 or 24)
+    pub ocp_level: f32,
+    /// OCP deglitch time
+    pub ocp_deglitch: OvercurrentDeglitch,
+    /// OCP fault mode
+    pub ocp_mode: OvercurrentMode,
+    /// Current sense amplifier gain
+    pub csa_gain: CurrentSenseAmplifierGain,
+    /// Buck output voltage in volts (3.3, 4.0, 5.0 or 5.7)
+    pub buck_voltage: f32,
+    /// Buck current limit class
+    pub buck_current_limit: BuckCurrentLimit,
+    /// Minimum ON time for the low side MOSFET
+    pub min_on_time: MinOnTime,
+}
+
+/// Values actually configured by [`PowerStage::apply`].
+///
+/// The `Display` implementation lists every value that was not represented exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerStageReport {
+    /// Slew rate in V/µs
+    pub slew_rate: Quantized<SlewRate>,
+    /// `GdConfig1::ocp_lvl` and the OCP level in amps
+    pub ocp_level: Quantized<bool>,
+    /// Buck output voltage in volts
+    pub buck_voltage: Quantized<BuckVoltage>,
+}
+
+impl PowerStageReport {
+    /// Returns true if every value was represented exactly.
+    pub fn is_exact(&self) -> bool {
+        self.slew_rate.is_exact() && self.ocp_level.is_exact() && self.buck_voltage.is_exact()
+    }
+}
+
+impl fmt::Display for PowerStageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_exact() {
+            return write!(f, "all values exact");
+        }
+        let values = [
+            (
+                "slew rate",
+                "V/µs",
+                self.slew_rate.requested,
+                self.slew_rate.actual,
+            ),
+            (
+                "OCP level",
+                "A",
+                self.ocp_level.requested,
+                self.ocp_level.actual,
+            ),
+            (
+                "buck voltage",
+                "V",
+                self.buck_voltage.requested,
+                self.buck_voltage.actual,
+            ),
+        ];
+        let mut separator = "";
+        for (name, unit, requested, actual) in values {
+            if requested != actual {
+                write!(
+                    f,
+                    "{separator}{name} {requested} {unit} set to {actual} {unit}"
+                )?;
+                separator = ", ";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PowerStage {
+    /// Reads the configuration out of `GdConfig1` and `GdConfig2`.
+    ///
+    /// Returns `None` if `slew_rate` or `ocp_mode` holds an undefined value.
+    pub fn from_registers(gd_config1: &GdConfig1, gd_config2: &GdConfig2) -> Option<Self> {
+        Some(PowerStage {
+            slew_rate: gd_config1.slew_rate()?.to_volts_per_us(),
+            ocp_level: gd_config1.ocp_level_amps(),
+            ocp_deglitch: gd_config1.ocp_deg(),
+            ocp_mode: gd_config1.ocp_mode()?,
+            csa_gain: gd_config1.csa_gain(),
+            buck_voltage: gd_config2.buck_sel().to_voltage(),
+            buck_current_limit: if gd_config2.buck_cl() {
+                BuckCurrentLimit::Ma150
+            } else {
+                BuckCurrentLimit::Ma600
+            },
+            min_on_time: gd_config2.min_on_time(),
+        })
+    }
+
+    /// Writes the configuration into `GdConfig1` and `GdConfig2`, leaving all other fields
+    /// untouched. Physical values snap to the nearest setting; nothing is written if one is
+    /// out of range.
+    pub fn apply(
+        &self,
+        gd_config1: &mut GdConfig1,
+        gd_config2: &mut GdConfig2,
+    ) -> Result<PowerStageReport, OutOfRangeError> {
+        let mut new_gd_config1 = *gd_config1;
+        let report = PowerStageReport {
+            slew_rate: SlewRate::from_volts_per_us(self.slew_rate)?,
+            ocp_level: new_gd_config1.set_ocp_level_amps(self.ocp_level)?,
+            buck_voltage: BuckVoltage::from_voltage(self.buck_voltage)?,
+        };
+
+        *gd_config1 = new_gd_config1;
+        gd_config1.set_slew_rate(Some(report.slew_rate.value));
+        gd_config1.set_ocp_deg(self.ocp_deglitch);
+        gd_config1.set_ocp_mode(Some(self.ocp_mode));
+        gd_config1.set_csa_gain(self.csa_gain);
+        gd_config2.set_buck_sel(report.buck_voltage.value);
+        gd_config2.set_buck_cl(self.buck_current_limit == BuckCurrentLimit::Ma150);
+        gd_config2.set_min_on_time(self.min_on_time);
+        Ok(report)
+    }
+}
@@ -5,10 +5,14 @@
 
 pub mod addresses;
 pub mod algorithm_configuration;
+pub mod algorithm_control;
+pub mod algorithm_variables;
 pub mod common;
 pub mod fault_configuration;
+pub mod fault_status;
 pub mod hardware_configuration;
 pub mod internal_algorithm_configuration;
 mod register;
+pub mod settings;
 
 pub use register::Register;
@@ -5,6 +5,25 @@ pub trait Register {
     /// 12-bit address of the register.
     const ADDRESS: u12;
 
+    /// Whether the register may be written while the motor is running.
+    ///
+    /// Status, control and pin override registers are safe. Startup, motor parameter, gate
+    /// driver and other configuration registers must only be written with the motor idle.
+    ///
+    /// The classification covers the whole register. PIN_CONFIG is safe for its SPEED and BRAKE
+    /// overrides, which also leaves its FG, alarm, brake pin mode and Vdc filter settings
+    /// writable while running. PERI_CONFIG1 and DEVICE_CONFIG1 hold configuration next to the
+    /// DIR override and the DAC enable, so they are unsafe; `MCF8316C::set_direction` and
+    /// `MCF8316C::route_dac` write them without the interlock.
+    /// Defaults to `false`, so registers defined outside this crate are treated as unsafe.
+    const SAFE_WHILE_RUNNING: bool = false;
+
+    /// Bits that read back the value written on the i2c bus.
+    ///
+    /// Parity, self-clearing, write 1 to clear and read-only bits are left out, so they are
+    /// ignored when a write is verified by reading the register back.
+    const VERIFY_MASK: u32 = 0xFFFF_FFFF;
+
     /// Returns the value to be sent on the i2c bus.
     fn value(&self) -> u32;
 
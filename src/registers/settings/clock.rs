@@ -0,0 +1,127 @@
+//! Clock source and spread spectrum modulation, spread across `DeviceConfig2` and `PeriConfig1`
+
+use core::fmt;
+
+use super::super::hardware_configuration::device_config2::{
+    ClockSource, DeviceConfig2, ExternalClockFrequency,
+};
+use super::super::hardware_configuration::peri_config1::PeriConfig1;
+use thiserror::Error;
+
+/// Clock driving the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSelection {
+    /// Internal oscillator
+    Internal,
+    /// Crude oscillator (WDT)
+    CrudeOscillator,
+    /// External clock input at the given frequency
+    External(ExternalClockFrequency),
+}
+
+/// Clock configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// Clock source
+    pub selection: ClockSelection,
+    /// Spread spectrum modulation (`PeriConfig1::spread_spectrum_modulation_disable` cleared)
+    pub spread_spectrum: bool,
+}
+
+/// Error returned for clock configurations that cannot be applied or decoded.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockError {
+    /// The external clock frequency is not one of the supported frequencies.
+    #[error("unsupported external clock frequency: {0} Hz")]
+    UnsupportedExternalFrequency(u32),
+    /// `DeviceConfig2::clk_sel` holds an undefined value.
+    #[error("undefined clock source")]
+    UndefinedSource,
+    /// The external clock is selected while external clock mode is disabled.
+    #[error("external clock selected while external clock mode is disabled")]
+    ExternalClockDisabled,
+}
+
+impl ClockConfig {
+    /// Internal oscillator with spread spectrum modulation, the reset configuration.
+    pub const INTERNAL: Self = ClockConfig {
+        selection: ClockSelection::Internal,
+        spread_spectrum: true,
+    };
+
+    /// External clock of `hz`, which must be one of the supported frequencies (8 kHz to
+    /// 1024 kHz in powers of two).
+    pub fn external(hz: u32, spread_spectrum: bool) -> Result<Self, ClockError> {
+        let frequency = ExternalClockFrequency::from_hz(hz)
+            .ok_or(ClockError::UnsupportedExternalFrequency(hz))?;
+        Ok(ClockConfig {
+            selection: ClockSelection::External(frequency),
+            spread_spectrum,
+        })
+    }
+
+    /// Reads the configuration out of `DeviceConfig2` and `PeriConfig1`.
+    pub fn from_registers(
+        device_config2: &DeviceConfig2,
+        peri_config1: &PeriConfig1,
+    ) -> Result<Self, ClockError> {
+        let source = device_config2
+            .clk_sel()
+            .ok_or(ClockError::UndefinedSource)?;
+        let selection = match source {
+            ClockSource::Internal => ClockSelection::Internal,
+            ClockSource::CrudeWdt => ClockSelection::CrudeOscillator,
+            ClockSource::External if device_config2.ext_clk_en() => {
+                ClockSelection::External(device_config2.ext_clk_config())
+            }
+            ClockSource::External => return Err(ClockError::ExternalClockDisabled),
+        };
+        Ok(ClockConfig {
+            selection,
+            spread_spectrum: !peri_config1.spread_spectrum_modulation_disable(),
+        })
+    }
+
+    /// Returns the `DeviceConfig2` values to write, in order, to switch from `current` to this
+    /// configuration.
+    ///
+    /// The external clock is configured and enabled before it is selected, and deselected
+    /// before it is disabled, so the device never runs from an unconfigured external clock.
+    pub fn device_config2_sequence(&self, current: DeviceConfig2) -> [DeviceConfig2; 2] {
+        match self.selection {
+            ClockSelection::External(frequency) => {
+                let prepared = current.with_ext_clk_config(frequency).with_ext_clk_en(true);
+                [prepared, prepared.with_clk_sel(Some(ClockSource::External))]
+            }
+            ClockSelection::Internal | ClockSelection::CrudeOscillator => {
+                let source = if self.selection == ClockSelection::Internal {
+                    ClockSource::Internal
+                } else {
+                    ClockSource::CrudeWdt
+                };
+                let switched = current.with_clk_sel(Some(source));
+                [switched, switched.with_ext_clk_en(false)]
+            }
+        }
+    }
+
+    /// Writes the spread spectrum setting into `PeriConfig1`, leaving all other fields
+    /// untouched.
+    pub fn apply_spread_spectrum(&self, peri_config1: &mut PeriConfig1) {
+        peri_config1.set_spread_spectrum_modulation_disable(!self.spread_spectrum);
+    }
+}
+
+impl fmt::Display for ClockConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.selection {
+            ClockSelection::Internal => write!(f, "{}", ClockSource::Internal)?,
+            ClockSelection::CrudeOscillator => write!(f, "{}", ClockSource::CrudeWdt)?,
+            ClockSelection::External(frequency) => {
+                write!(f, "{} at {}", ClockSource::External, frequency)?
+            }
+        }
+        let ssm = if self.spread_spectrum { "on" } else { "off" };
+        write!(f, ", spread spectrum {ssm}")
+    }
+}
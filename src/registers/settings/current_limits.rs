@@ -0,0 +1,130 @@
+//! Current limits, spread across `FaultConfig1`, `MotorStartup1`, `MotorStartup2`, `PeriConfig1`
+//! and `GdConfig1`
+
+use super::super::algorithm_configuration::motor_startup1::MotorStartup1;
+use super::super::algorithm_configuration::motor_startup2::MotorStartup2;
+use super::super::fault_configuration::fault_config1::FaultConfig1;
+use super::super::hardware_configuration::device_config2::DeviceConfig2;
+use super::super::hardware_configuration::gd_config1::{CurrentSenseAmplifierGain, GdConfig1};
+use super::super::hardware_configuration::peri_config1::PeriConfig1;
+use super::*;
+use thiserror::Error;
+
+/// Current limit configuration in amps.
+///
+/// All software limits snap to the nearest `CurrentSelection`. The overcurrent protection level
+/// snaps to 16 A or 24 A.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurrentLimits {
+    /// Current limit for Iq axis (torque) current reference in closed loop (`FaultConfig1::ilimit`)
+    pub ilimit: f32,
+    /// ADC based lock detection current threshold (`FaultConfig1::lock_ilimit`)
+    pub lock_ilimit: f32,
+    /// Comparator based lock detection current threshold (`FaultConfig1::hw_lock_ilimit`)
+    pub hw_lock_ilimit: f32,
+    /// Open loop current limit (`MotorStartup2::ol_ilimit`)
+    pub ol_ilimit: f32,
+    /// Align or slow first cycle current limit (`MotorStartup1::align_or_slow_current_ilimit`)
+    pub align_ilimit: f32,
+    /// Bus current limit (`PeriConfig1::bus_current_limit`), or `None` to disable it
+    pub bus_current_limit: Option<f32>,
+    /// Overcurrent protection level (`GdConfig1::ocp_lvl`)
+    pub ocp_level: f32,
+}
+
+/// Error returned for current limits that cannot be applied.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum CurrentLimitsError {
+    /// A current cannot be represented by its register field.
+    #[error("current out of range: {0}")]
+    OutOfRange(#[from] OutOfRangeError),
+    /// A limit lies beyond what the current sense amplifier can measure at the configured gain.
+    #[error(
+        "current limit {limit} A exceeds the {full_scale} A range of the current sense amplifier"
+    )]
+    ExceedsCsaRange {
+        /// Limit in amps, after quantization.
+        limit: f32,
+        /// Largest measurable current in amps.
+        full_scale: f32,
+    },
+    /// The ADC based lock threshold is above the comparator based lock threshold.
+    #[error(
+        "lock current limit {lock_ilimit} A is above hardware lock current limit {hw_lock_ilimit} A"
+    )]
+    LockAboveHardwareLock {
+        /// ADC based lock threshold in amps, after quantization.
+        lock_ilimit: f32,
+        /// Comparator based lock threshold in amps, after quantization.
+        hw_lock_ilimit: f32,
+    },
+}
+
+impl CurrentLimits {
+    /// Returns the largest current the configured current sense amplifier setup can measure.
+    ///
+    /// With dynamic CSA gain enabled the device picks the gain itself, so the lowest gain (widest
+    /// range) applies. Otherwise `GdConfig1::csa_gain` is used.
+    pub fn measurable_current(gd_config1: &GdConfig1, device_config2: &DeviceConfig2) -> f32 {
+        if device_config2.dynamic_csa_gain_en() {
+            CurrentSenseAmplifierGain::VA0_15.full_scale_current()
+        } else {
+            gd_config1.csa_gain().full_scale_current()
+        }
+    }
+
+    /// Validates the limits against each other and against the current sense amplifier range, then
+    /// writes them into the registers, leaving all other fields untouched. Nothing is written if
+    /// the limits are rejected.
+    ///
+    /// `device_config2` is only read, to find out whether dynamic CSA gain is enabled.
+    pub fn apply(
+        &self,
+        fault_config1: &mut FaultConfig1,
+        motor_startup1: &mut MotorStartup1,
+        motor_startup2: &mut MotorStartup2,
+        peri_config1: &mut PeriConfig1,
+        gd_config1: &mut GdConfig1,
+        device_config2: &DeviceConfig2,
+    ) -> Result<(), CurrentLimitsError> {
+        let ilimit = CurrentSelection::from_amps(self.ilimit)?;
+        let lock_ilimit = CurrentSelection::from_amps(self.lock_ilimit)?;
+        let hw_lock_ilimit = CurrentSelection::from_amps(self.hw_lock_ilimit)?;
+        let ol_ilimit = CurrentSelection::from_amps(self.ol_ilimit)?;
+        let align_ilimit = CurrentSelection::from_amps(self.align_ilimit)?;
+        let bus_current_limit = self
+            .bus_current_limit
+            .map(CurrentSelection::from_amps)
+            .transpose()?;
+        let mut new_gd_config1 = *gd_config1;
+        new_gd_config1.set_ocp_level_amps(self.ocp_level)?;
+
+        let full_scale = Self::measurable_current(gd_config1, device_config2);
+        for limit in [ilimit, lock_ilimit, hw_lock_ilimit, ol_ilimit, align_ilimit] {
+            if limit.actual > full_scale {
+                return Err(CurrentLimitsError::ExceedsCsaRange {
+                    limit: limit.actual,
+                    full_scale,
+                });
+            }
+        }
+        if lock_ilimit.actual > hw_lock_ilimit.actual {
+            return Err(CurrentLimitsError::LockAboveHardwareLock {
+                lock_ilimit: lock_ilimit.actual,
+                hw_lock_ilimit: hw_lock_ilimit.actual,
+            });
+        }
+
+        fault_config1.set_ilimit(ilimit.value);
+        fault_config1.set_lock_ilimit(lock_ilimit.value);
+        fault_config1.set_hw_lock_ilimit(hw_lock_ilimit.value);
+        motor_startup1.set_align_or_slow_current_ilimit(align_ilimit.value);
+        motor_startup2.set_ol_ilimit(ol_ilimit.value);
+        peri_config1.set_bus_current_limit_en(bus_current_limit.is_some());
+        if let Some(bus_current_limit) = bus_current_limit {
+            peri_config1.set_bus_current_limit(bus_current_limit.value);
+        }
+        *gd_config1 = new_gd_config1;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! FG output configuration, spread across `ClosedLoop1` and `PinConfig`
+
+use core::num::NonZeroU8;
+
+use super::super::algorithm_configuration::closed_loop1::{
+    ClosedLoop1, FgBemfThreshold, FgDiv, FgSelect,
+};
+use super::super::hardware_configuration::pin_config::{FgFaultConfig, FgIdleConfig, PinConfig};
+
+/// How long FG keeps toggling once the motor is no longer driven.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FgStopBehavior {
+    /// FG is active as long as the motor is driven.
+    WhileDriven,
+    /// FG is active until BEMF drops below the threshold.
+    UntilBemfBelow(FgBemfThreshold),
+}
+
+/// FG output configuration.
+///
+/// The conversions between FG frequency and motor speed use `div`, so firmware measuring FG and
+/// the device configuration agree on the divisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FgOutput {
+    /// When FG is output (`ClosedLoop1::fg_sel`)
+    pub select: FgSelect,
+    /// Division factor (`ClosedLoop1::fg_div`)
+    pub div: FgDiv,
+    /// Behavior when the motor is no longer driven (`ClosedLoop1::fg_config` and
+    /// `ClosedLoop1::fg_bemf_thr`)
+    pub stop_behavior: FgStopBehavior,
+    /// FG state during stop (`PinConfig::fg_idle_config`)
+    pub idle: FgIdleConfig,
+    /// FG state during fault (`PinConfig::fg_fault_config`)
+    pub fault: FgFaultConfig,
+}
+
+impl FgOutput {
+    /// Creates an FG output in open and closed loop, with FG following the drive state during
+    /// stop and fault.
+    pub const fn new(div: FgDiv) -> Self {
+        FgOutput {
+            select: FgSelect::OpenClosed,
+            div,
+            stop_behavior: FgStopBehavior::WhileDriven,
+            idle: FgIdleConfig::FgConfig,
+            fault: FgFaultConfig::FgConfig,
+        }
+    }
+
+    /// Creates an FG output whose frequency equals the mechanical speed of a motor with the
+    /// given number of pole pairs. Returns `None` if the count is not between 1 and 15.
+    pub fn mechanical(pole_pairs: u8) -> Option<Self> {
+        FgDiv::for_pole_pairs(pole_pairs).map(Self::new)
+    }
+
+    /// Sets when FG is output.
+    pub const fn with_select(mut self, select: FgSelect) -> Self {
+        self.select = select;
+        self
+    }
+
+    /// Sets the behavior when the motor is no longer driven.
+    pub const fn with_stop_behavior(mut self, stop_behavior: FgStopBehavior) -> Self {
+        self.stop_behavior = stop_behavior;
+        self
+    }
+
+    /// Sets the FG state during stop.
+    pub const fn with_idle(mut self, idle: FgIdleConfig) -> Self {
+        self.idle = idle;
+        self
+    }
+
+    /// Sets the FG state during fault.
+    pub const fn with_fault(mut self, fault: FgFaultConfig) -> Self {
+        self.fault = fault;
+        self
+    }
+
+    /// Returns the mechanical speed in RPM for a measured FG frequency in Hz.
+    pub fn rpm_from_fg(&self, fg_hz: f32, pole_pairs: NonZeroU8) -> f32 {
+        self.div.mechanical_rpm(fg_hz, pole_pairs)
+    }
+
+    /// Returns the expected FG frequency in Hz at a mechanical speed in RPM.
+    pub fn fg_from_rpm(&self, rpm: f32, pole_pairs: NonZeroU8) -> f32 {
+        self.div.fg_hz_from_rpm(rpm, pole_pairs)
+    }
+
+    /// Reads the configuration out of `ClosedLoop1` and `PinConfig`.
+    ///
+    /// Returns `None` if `fg_sel` or `fg_bemf_thr` holds an undefined value.
+    pub fn from_registers(closed_loop1: &ClosedLoop1, pin_config: &PinConfig) -> Option<Self> {
+        let stop_behavior = if closed_loop1.fg_config() {
+            FgStopBehavior::UntilBemfBelow(closed_loop1.fg_bemf_thr()?)
+        } else {
+            FgStopBehavior::WhileDriven
+        };
+        Some(FgOutput {
+            select: closed_loop1.fg_sel()?,
+            div: closed_loop1.fg_div(),
+            stop_behavior,
+            idle: pin_config.fg_idle_config(),
+            fault: pin_config.fg_fault_config(),
+        })
+    }
+
+    /// Writes the configuration into `ClosedLoop1` and `PinConfig`, leaving all other fields
+    /// untouched.
+    pub fn apply(&self, closed_loop1: &mut ClosedLoop1, pin_config: &mut PinConfig) {
+        closed_loop1.set_fg_sel(Some(self.select));
+        closed_loop1.set_fg_div(self.div);
+        match self.stop_behavior {
+            FgStopBehavior::WhileDriven => closed_loop1.set_fg_config(false),
+            FgStopBehavior::UntilBemfBelow(threshold) => {
+                closed_loop1.set_fg_config(true);
+                closed_loop1.set_fg_bemf_thr(Some(threshold));
+            }
+        }
+        pin_config.set_fg_idle_config(self.idle);
+        pin_config.set_fg_fault_config(self.fault);
+    }
+}
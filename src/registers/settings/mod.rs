@@ -0,0 +1,16 @@
+//! Settings that span registers from more than one configuration section
+//!
+//! Each type here takes its parameters in physical units, checks them against each other and
+//! writes every register field involved in one step.
+//!
+
+pub mod clock;
+pub mod current_limits;
+pub mod fg_output;
+pub mod speed_input;
+pub mod stop_policy;
+pub mod voltage_protection;
+pub mod windmilling;
+
+pub use super::common::*;
+pub use super::register::Register;
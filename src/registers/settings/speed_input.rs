@@ -0,0 +1,203 @@
+//! Speed input configuration, spread across `PinConfig`, `DeviceConfig2`, `IntAlgo1`,
+//! `PeriConfig1` and `RefProfiles3`
+
+use super::super::algorithm_configuration::closed_loop4::ClosedLoop4;
+use super::super::algorithm_configuration::ref_profiles3::{DutyHysteresis, RefProfiles3};
+use super::super::algorithm_configuration::reference_profile::{
+    ProfileEvaluator, ReferenceProfile, SpeedReference,
+};
+use super::super::hardware_configuration::device_config2::DeviceConfig2;
+use super::super::hardware_configuration::peri_config1::PeriConfig1;
+use super::super::hardware_configuration::pin_config::{PinConfig, SpeedMode};
+use super::super::internal_algorithm_configuration::int_algo1::{IntAlgo1, SpeedPinGlitchFilter};
+use super::*;
+use arbitrary_int::*;
+
+/// SPEED pin voltage corresponding to 100% duty in analog mode.
+pub const ANALOG_FULL_SCALE_VOLTS: f32 = 3.0;
+
+/// Largest DIGITAL_SPEED_CTRL value, corresponding to 100% duty in I2C mode.
+pub const DIGITAL_SPEED_CTRL_MAX: u16 = 0x7FFF;
+
+/// Frequency range of the PWM input (`PeriConfig1::speed_range_sel`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmInputRange {
+    /// 325 Hz to 100 kHz
+    Hz325To100k,
+    /// 10 Hz to 325 Hz
+    Hz10To325,
+}
+
+/// Source of the speed command, with the parameters that apply to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpeedInput {
+    /// Analog voltage on the SPEED pin
+    Analog,
+    /// Duty cycle on the SPEED pin
+    Pwm {
+        /// PWM frequency range
+        range: PwmInputRange,
+        /// Glitch filter applied on the SPEED pin
+        glitch_filter: SpeedPinGlitchFilter,
+    },
+    /// DIGITAL_SPEED_CTRL written over I2C
+    I2C,
+    /// Frequency on the SPEED pin
+    Frequency {
+        /// Input frequency in Hz corresponding to 100% duty
+        max_input_hz: f32,
+        /// Glitch filter applied on the SPEED pin
+        glitch_filter: SpeedPinGlitchFilter,
+    },
+}
+
+impl SpeedInput {
+    /// Returns the `SpeedMode` this input is encoded as.
+    pub fn mode(&self) -> SpeedMode {
+        match self {
+            SpeedInput::Analog => SpeedMode::Analog,
+            SpeedInput::Pwm { .. } => SpeedMode::Pwm,
+            SpeedInput::I2C => SpeedMode::I2C,
+            SpeedInput::Frequency { .. } => SpeedMode::Frequency,
+        }
+    }
+
+    /// Converts an input value into the duty cycle fed to the reference profile.
+    ///
+    /// `value` is in volts for analog input, % duty for PWM input, the raw DIGITAL_SPEED_CTRL
+    /// value for I2C input and Hz for frequency input. Duty cycles above 100% are clamped.
+    pub fn duty(&self, value: f32) -> PercentAsU8 {
+        let percent = match self {
+            SpeedInput::Analog => value / ANALOG_FULL_SCALE_VOLTS * 100.0,
+            SpeedInput::Pwm { .. } => value,
+            SpeedInput::I2C => value / DIGITAL_SPEED_CTRL_MAX as f32 * 100.0,
+            SpeedInput::Frequency { max_input_hz, .. } => value / max_input_hz * 100.0,
+        };
+        PercentAsU8::new((percent.clamp(0.0, 100.0) * 2.55 + 0.5) as u8)
+    }
+}
+
+/// Speed input configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedInputConfig {
+    /// Speed command source
+    pub input: SpeedInput,
+    /// Duty hysteresis of the reference profile (`RefProfiles3::duty_hys`)
+    pub duty_hysteresis: DutyHysteresis,
+}
+
+impl SpeedInputConfig {
+    /// Reads the configuration out of the speed input registers.
+    pub fn from_registers(
+        pin_config: &PinConfig,
+        device_config2: &DeviceConfig2,
+        int_algo1: &IntAlgo1,
+        peri_config1: &PeriConfig1,
+        ref_profiles3: &RefProfiles3,
+    ) -> Self {
+        let glitch_filter = int_algo1.speed_pin_glitch_filter();
+        let input = match pin_config.speed_mode() {
+            SpeedMode::Analog => SpeedInput::Analog,
+            SpeedMode::Pwm => SpeedInput::Pwm {
+                range: if peri_config1.speed_range_sel() {
+                    PwmInputRange::Hz10To325
+                } else {
+                    PwmInputRange::Hz325To100k
+                },
+                glitch_filter,
+            },
+            SpeedMode::I2C => SpeedInput::I2C,
+            SpeedMode::Frequency => SpeedInput::Frequency {
+                max_input_hz: device_config2.input_maximum_freq().value() as f32,
+                glitch_filter,
+            },
+        };
+        SpeedInputConfig {
+            input,
+            duty_hysteresis: ref_profiles3.duty_hys(),
+        }
+    }
+
+    /// Writes the configuration into the speed input registers, leaving all other fields
+    /// untouched.
+    ///
+    /// Returns the maximum input frequency actually configured in frequency mode, rounded to the
+    /// nearest Hz, and `None` in the other modes. The registers are left unchanged on error.
+    pub fn apply(
+        &self,
+        pin_config: &mut PinConfig,
+        device_config2: &mut DeviceConfig2,
+        int_algo1: &mut IntAlgo1,
+        peri_config1: &mut PeriConfig1,
+        ref_profiles3: &mut RefProfiles3,
+    ) -> Result<Option<Quantized<u15>>, OutOfRangeError> {
+        let mut max_input_freq = None;
+        match self.input {
+            SpeedInput::Analog | SpeedInput::I2C => {}
+            SpeedInput::Pwm {
+                range,
+                glitch_filter,
+            } => {
+                peri_config1.set_speed_range_sel(range == PwmInputRange::Hz10To325);
+                int_algo1.set_speed_pin_glitch_filter(glitch_filter);
+            }
+            SpeedInput::Frequency {
+                max_input_hz,
+                glitch_filter,
+            } => {
+                let hz = OutOfRangeError::check(max_input_hz, 1.0, u15::MAX.value() as f32)?;
+                let value = u15::new((hz + 0.5) as u16);
+                device_config2.set_input_maximum_freq(value);
+                int_algo1.set_speed_pin_glitch_filter(glitch_filter);
+                max_input_freq = Some(Quantized {
+                    value,
+                    requested: max_input_hz,
+                    actual: value.value() as f32,
+                });
+            }
+        }
+        pin_config.set_speed_mode(self.input.mode());
+        ref_profiles3.set_duty_hys(self.duty_hysteresis);
+        Ok(max_input_freq)
+    }
+
+    /// Creates a map from input values to commanded speed through the given reference profile.
+    ///
+    /// The profile's duty hysteresis is replaced by `duty_hysteresis`, so the map behaves like the
+    /// device once this configuration is applied.
+    pub fn map(&self, profile: ReferenceProfile, closed_loop4: &ClosedLoop4) -> SpeedInputMap {
+        SpeedInputMap {
+            input: self.input,
+            evaluator: ProfileEvaluator::new(ReferenceProfile {
+                duty_hys: self.duty_hysteresis,
+                ..profile
+            }),
+            max_speed_hz: closed_loop4.max_speed_hz(),
+        }
+    }
+}
+
+/// Maps input values to the speed commanded by the device.
+///
+/// Holds the reference profile state, so values must be applied in the order they are seen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedInputMap {
+    input: SpeedInput,
+    evaluator: ProfileEvaluator,
+    max_speed_hz: f32,
+}
+
+impl SpeedInputMap {
+    /// Applies the next input value and returns the commanded reference.
+    ///
+    /// See [`SpeedInput::duty`] for the unit of `value`.
+    pub fn update(&mut self, value: f32) -> SpeedReference {
+        self.evaluator.update(self.input.duty(value))
+    }
+
+    /// Applies the next input value and returns the commanded electrical speed in Hz.
+    /// Negative speeds command the reverse direction.
+    pub fn update_hz(&mut self, value: f32) -> f32 {
+        self.update(value).percent / 100.0 * self.max_speed_hz
+    }
+}
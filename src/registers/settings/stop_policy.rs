@@ -0,0 +1,313 @@
+//! Motor stop and brake policy, spread across `ClosedLoop2`, `PinConfig`, `MotorStartup1`,
+//! `IntAlgo2`, `RevDriveConfig` and `PeriConfig1`
+
+use super::super::algorithm_configuration::closed_loop2::{
+    ClosedLoop2, MotorStop, MotorStopBrakeTime, PercentDecreasing,
+};
+use super::super::algorithm_configuration::motor_startup1::MotorStartup1;
+use super::super::algorithm_configuration::rev_drive_config::{
+    ActiveBrakeCurrentLimit, RevDriveConfig,
+};
+use super::super::hardware_configuration::peri_config1::{
+    BrakeDeltaLimit, ModulationIndexLimit, PeriConfig1,
+};
+use super::super::hardware_configuration::pin_config::{BrakeInput, PinConfig};
+use super::super::internal_algorithm_configuration::int_algo2::{
+    ActiveBrakeBusCurrentSlewRate, IntAlgo2,
+};
+use arbitrary_int::*;
+
+/// What the device does with the motor when it is commanded to stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopMode {
+    /// Hi-Z; the motor coasts.
+    Coast,
+    /// Low side braking for the given time once below the brake speed threshold.
+    LowSideBrake(MotorStopBrakeTime),
+    /// High side braking for the given time once below the brake speed threshold.
+    HighSideBrake(MotorStopBrakeTime),
+    /// Align braking for the given time once below the brake speed threshold.
+    AlignBrake(MotorStopBrakeTime),
+    /// Active spin down until the given speed threshold (% of MAX_SPEED).
+    ActiveSpinDown(PercentDecreasing),
+}
+
+impl StopMode {
+    /// Returns the `MotorStop` option this mode is encoded as.
+    pub fn motor_stop(&self) -> MotorStop {
+        match self {
+            StopMode::Coast => MotorStop::HiZ,
+            StopMode::LowSideBrake(_) => MotorStop::LowSideBraking,
+            StopMode::HighSideBrake(_) => MotorStop::HighSideBraking,
+            StopMode::AlignBrake(_) => MotorStop::AlignBraking,
+            StopMode::ActiveSpinDown(_) => MotorStop::ActiveSpinDown,
+        }
+    }
+
+    fn uses_brake_speed_threshold(&self) -> bool {
+        matches!(
+            self,
+            StopMode::LowSideBrake(_) | StopMode::HighSideBrake(_) | StopMode::AlignBrake(_)
+        )
+    }
+}
+
+/// Angle used for align braking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignBrakeAngle {
+    /// Last commutation angle before entering align braking
+    LastCommutation,
+    /// `MotorStartup2::align_angle`
+    AlignAngle,
+}
+
+/// Action taken when the BRAKE pin (or its override) requests braking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrakePinMode {
+    /// Low side brake
+    LowSide,
+    /// Align brake
+    Align,
+}
+
+/// BRAKE pin configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrakePin {
+    /// Hardware pin or override
+    pub input: BrakeInput,
+    /// Brake action
+    pub mode: BrakePinMode,
+}
+
+/// Active braking during deceleration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveBrake {
+    /// Bus current limit during active braking (`RevDriveConfig::active_brake_current_limit`)
+    pub current_limit: ActiveBrakeCurrentLimit,
+    /// Active braking loop Kp = `kp` / 2^7 (`RevDriveConfig::active_brake_kp`)
+    pub kp: u10,
+    /// Active braking loop Ki = `ki` / 2^9 (`RevDriveConfig::active_brake_ki`)
+    pub ki: u10,
+    /// Bus current slew rate (`IntAlgo2::active_brake_bus_current_slew_rate`)
+    pub slew_rate: ActiveBrakeBusCurrentSlewRate,
+    /// Speed difference below which active braking is applied
+    /// (`PeriConfig1::active_brake_speed_delta_limit_entry`)
+    pub speed_delta_limit_entry: BrakeDeltaLimit,
+    /// Modulation index below which active braking is applied
+    /// (`PeriConfig1::active_brake_mod_index_limit`)
+    pub mod_index_limit: ModulationIndexLimit,
+}
+
+/// Stop and brake behavior, configured as a single policy.
+///
+/// Only the fields used by the policy are written; for example the brake time is left untouched
+/// when coasting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopPolicy {
+    /// Motor stop option
+    pub mode: StopMode,
+    /// Speed threshold for the BRAKE pin and the braking stop modes (% of MAX_SPEED)
+    pub brake_speed_threshold: PercentDecreasing,
+    /// Angle used by align braking, from the stop mode or the BRAKE pin
+    pub align_brake_angle: AlignBrakeAngle,
+    /// BRAKE pin configuration, or `None` to ignore the pin
+    pub brake_pin: Option<BrakePin>,
+    /// Active braking during deceleration, or `None` to disable it
+    pub active_brake: Option<ActiveBrake>,
+}
+
+impl StopPolicy {
+    const fn with_mode(mode: StopMode) -> Self {
+        StopPolicy {
+            mode,
+            brake_speed_threshold: PercentDecreasing::P100,
+            align_brake_angle: AlignBrakeAngle::LastCommutation,
+            brake_pin: None,
+            active_brake: None,
+        }
+    }
+
+    /// Lets the motor coast (Hi-Z).
+    pub const fn coast() -> Self {
+        Self::with_mode(StopMode::Coast)
+    }
+
+    /// Brakes with the low side switches below `speed_threshold`.
+    pub const fn low_side_brake(
+        time: MotorStopBrakeTime,
+        speed_threshold: PercentDecreasing,
+    ) -> Self {
+        Self::with_mode(StopMode::LowSideBrake(time)).with_brake_speed_threshold(speed_threshold)
+    }
+
+    /// Brakes with the high side switches below `speed_threshold`.
+    pub const fn high_side_brake(
+        time: MotorStopBrakeTime,
+        speed_threshold: PercentDecreasing,
+    ) -> Self {
+        Self::with_mode(StopMode::HighSideBrake(time)).with_brake_speed_threshold(speed_threshold)
+    }
+
+    /// Aligns the rotor at `angle` below `speed_threshold`.
+    pub const fn align_brake(
+        time: MotorStopBrakeTime,
+        speed_threshold: PercentDecreasing,
+        angle: AlignBrakeAngle,
+    ) -> Self {
+        Self::with_mode(StopMode::AlignBrake(time))
+            .with_brake_speed_threshold(speed_threshold)
+            .with_align_brake_angle(angle)
+    }
+
+    /// Actively decelerates the motor down to `speed_threshold`.
+    pub const fn active_spin_down(speed_threshold: PercentDecreasing) -> Self {
+        Self::with_mode(StopMode::ActiveSpinDown(speed_threshold))
+    }
+
+    /// Sets the speed threshold for the BRAKE pin and the braking stop modes.
+    pub const fn with_brake_speed_threshold(mut self, speed_threshold: PercentDecreasing) -> Self {
+        self.brake_speed_threshold = speed_threshold;
+        self
+    }
+
+    /// Sets the angle used by align braking.
+    pub const fn with_align_brake_angle(mut self, angle: AlignBrakeAngle) -> Self {
+        self.align_brake_angle = angle;
+        self
+    }
+
+    /// Enables the BRAKE pin.
+    pub const fn with_brake_pin(mut self, brake_pin: BrakePin) -> Self {
+        self.brake_pin = Some(brake_pin);
+        self
+    }
+
+    /// Enables active braking during deceleration.
+    pub const fn with_active_brake(mut self, active_brake: ActiveBrake) -> Self {
+        self.active_brake = Some(active_brake);
+        self
+    }
+
+    fn uses_align_brake_angle(&self) -> bool {
+        matches!(self.mode, StopMode::AlignBrake(_))
+            || matches!(
+                self.brake_pin,
+                Some(BrakePin {
+                    mode: BrakePinMode::Align,
+                    ..
+                })
+            )
+    }
+
+    /// Reads the policy out of the stop and brake registers.
+    ///
+    /// Returns `None` if `ClosedLoop2::mtr_stop` holds an undefined or not applicable value.
+    pub fn from_registers(
+        closed_loop2: &ClosedLoop2,
+        pin_config: &PinConfig,
+        motor_startup1: &MotorStartup1,
+        int_algo2: &IntAlgo2,
+        rev_drive_config: &RevDriveConfig,
+        peri_config1: &PeriConfig1,
+    ) -> Option<Self> {
+        let time = closed_loop2.mtr_stop_brk_time();
+        let mode = match closed_loop2.mtr_stop()? {
+            MotorStop::HiZ => StopMode::Coast,
+            MotorStop::NotApplicable => return None,
+            MotorStop::LowSideBraking => StopMode::LowSideBrake(time),
+            MotorStop::HighSideBraking => StopMode::HighSideBrake(time),
+            MotorStop::AlignBraking => StopMode::AlignBrake(time),
+            MotorStop::ActiveSpinDown => StopMode::ActiveSpinDown(closed_loop2.act_spin_thr()),
+        };
+        let brake_pin = match pin_config.brake_input() {
+            BrakeInput::OverrideNoBrake => None,
+            input => Some(BrakePin {
+                input,
+                mode: if pin_config.brake_pin_mode() {
+                    BrakePinMode::Align
+                } else {
+                    BrakePinMode::LowSide
+                },
+            }),
+        };
+        let active_brake = match peri_config1.active_brake_speed_delta_limit_entry() {
+            Some(speed_delta_limit_entry) if motor_startup1.active_brake_en() => {
+                Some(ActiveBrake {
+                    current_limit: rev_drive_config.active_brake_current_limit(),
+                    kp: rev_drive_config.active_brake_kp(),
+                    ki: rev_drive_config.active_brake_ki(),
+                    slew_rate: int_algo2.active_brake_bus_current_slew_rate(),
+                    speed_delta_limit_entry,
+                    mod_index_limit: peri_config1.active_brake_mod_index_limit(),
+                })
+            }
+            _ => None,
+        };
+        Some(StopPolicy {
+            mode,
+            brake_speed_threshold: closed_loop2.brake_speed_threshold(),
+            align_brake_angle: if pin_config.align_brake_angle_sel() {
+                AlignBrakeAngle::AlignAngle
+            } else {
+                AlignBrakeAngle::LastCommutation
+            },
+            brake_pin,
+            active_brake,
+        })
+    }
+
+    /// Writes the policy into the stop and brake registers.
+    ///
+    /// Fields the policy does not use keep their value, except that the BRAKE pin is overridden
+    /// to not brake when `brake_pin` is `None` and active braking is disabled when
+    /// `active_brake` is `None`.
+    pub fn apply(
+        &self,
+        closed_loop2: &mut ClosedLoop2,
+        pin_config: &mut PinConfig,
+        motor_startup1: &mut MotorStartup1,
+        int_algo2: &mut IntAlgo2,
+        rev_drive_config: &mut RevDriveConfig,
+        peri_config1: &mut PeriConfig1,
+    ) {
+        closed_loop2.set_mtr_stop(Some(self.mode.motor_stop()));
+        match self.mode {
+            StopMode::LowSideBrake(time)
+            | StopMode::HighSideBrake(time)
+            | StopMode::AlignBrake(time) => {
+                closed_loop2.set_mtr_stop_brk_time(time);
+            }
+            StopMode::ActiveSpinDown(speed_threshold) => {
+                closed_loop2.set_act_spin_thr(speed_threshold);
+            }
+            StopMode::Coast => {}
+        }
+        if self.mode.uses_brake_speed_threshold() || self.brake_pin.is_some() {
+            closed_loop2.set_brake_speed_threshold(self.brake_speed_threshold);
+        }
+        if self.uses_align_brake_angle() {
+            pin_config
+                .set_align_brake_angle_sel(self.align_brake_angle == AlignBrakeAngle::AlignAngle);
+        }
+
+        match self.brake_pin {
+            Some(brake_pin) => {
+                pin_config.set_brake_input(brake_pin.input);
+                pin_config.set_brake_pin_mode(brake_pin.mode == BrakePinMode::Align);
+            }
+            None => pin_config.set_brake_input(BrakeInput::OverrideNoBrake),
+        }
+
+        motor_startup1.set_active_brake_en(self.active_brake.is_some());
+        if let Some(active_brake) = self.active_brake {
+            rev_drive_config.set_active_brake_current_limit(active_brake.current_limit);
+            rev_drive_config.set_active_brake_kp(active_brake.kp);
+            rev_drive_config.set_active_brake_ki(active_brake.ki);
+            int_algo2.set_active_brake_bus_current_slew_rate(active_brake.slew_rate);
+            peri_config1.set_active_brake_speed_delta_limit_entry(Some(
+                active_brake.speed_delta_limit_entry,
+            ));
+            peri_config1.set_active_brake_mod_index_limit(active_brake.mod_index_limit);
+        }
+    }
+}
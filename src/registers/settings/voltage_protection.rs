@@ -0,0 +1,143 @@
+//! Bus voltage protection, spread across `FaultConfig2`, `DeviceConfig1` and `GdConfig1`
+
+use super::super::fault_configuration::fault_config2::{
+    FaultConfig2, MaximumBusVoltage, MinimumBusVoltage,
+};
+use super::super::hardware_configuration::device_config1::{DeviceConfig1, MaxBusVoltage};
+use super::super::hardware_configuration::gd_config1::GdConfig1;
+use super::*;
+use thiserror::Error;
+
+/// Recovery behavior after a bus voltage fault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoltageFaultRecovery {
+    /// The fault is latched until cleared.
+    Latch,
+    /// The fault clears automatically once the voltage is back in bounds.
+    AutoClear,
+}
+
+/// A bus voltage threshold and the reaction when it is crossed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoltageLimit {
+    /// Threshold in volts.
+    pub volts: f32,
+    /// Recovery behavior once the threshold is crossed.
+    pub recovery: VoltageFaultRecovery,
+}
+
+/// Bus voltage protection configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoltageProtection {
+    /// Highest DC bus voltage expected during normal operation, in volts.
+    /// Selects `DeviceConfig1::bus_volt` together with the overvoltage threshold.
+    pub max_bus_voltage: f32,
+    /// Minimum DC bus voltage for running the motor (4.5 V to 12.5 V), or `None` for no limit.
+    pub undervoltage: Option<VoltageLimit>,
+    /// Maximum DC bus voltage for running the motor (20 V to 35 V), or `None` for no limit.
+    pub overvoltage: Option<VoltageLimit>,
+    /// Hardware overvoltage protection level (22 V or 34 V), or `None` to disable it.
+    pub hardware_ovp: Option<f32>,
+}
+
+/// Error returned for voltage protection settings that cannot be applied.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum VoltageProtectionError {
+    /// A voltage cannot be represented by its register field.
+    #[error("voltage out of range: {0}")]
+    OutOfRange(#[from] OutOfRangeError),
+    /// The undervoltage threshold is not below the overvoltage threshold.
+    #[error(
+        "undervoltage threshold {undervoltage} V is not below overvoltage threshold {overvoltage} V"
+    )]
+    UndervoltageAboveOvervoltage {
+        /// Undervoltage threshold in volts, after quantization.
+        undervoltage: f32,
+        /// Overvoltage threshold in volts, after quantization.
+        overvoltage: f32,
+    },
+    /// The overvoltage threshold would trip during normal operation.
+    #[error("overvoltage threshold {overvoltage} V is below the maximum bus voltage {bus} V")]
+    OvervoltageBelowBusVoltage {
+        /// Overvoltage threshold in volts, after quantization.
+        overvoltage: f32,
+        /// Maximum expected bus voltage in volts.
+        bus: f32,
+    },
+    /// The hardware overvoltage protection would trip before the overvoltage threshold, or
+    /// during normal operation.
+    #[error("overvoltage protection level {ovp} V is below the maximum operating voltage {max} V")]
+    OvpBelowMaximumVoltage {
+        /// Hardware overvoltage protection level in volts.
+        ovp: f32,
+        /// Overvoltage threshold, or maximum bus voltage when there is no threshold, in volts.
+        max: f32,
+    },
+}
+
+impl VoltageProtection {
+    /// Validates the configuration and writes it into the three registers, leaving all other
+    /// fields untouched. Nothing is written if the configuration is rejected.
+    pub fn apply(
+        &self,
+        fault_config2: &mut FaultConfig2,
+        device_config1: &mut DeviceConfig1,
+        gd_config1: &mut GdConfig1,
+    ) -> Result<(), VoltageProtectionError> {
+        let undervoltage = self
+            .undervoltage
+            .map(|limit| MinimumBusVoltage::from_volts(limit.volts))
+            .transpose()?;
+        let overvoltage = self
+            .overvoltage
+            .map(|limit| MaximumBusVoltage::from_volts(limit.volts))
+            .transpose()?;
+
+        if let (Some(under), Some(over)) = (undervoltage, overvoltage)
+            && under.actual >= over.actual
+        {
+            return Err(VoltageProtectionError::UndervoltageAboveOvervoltage {
+                undervoltage: under.actual,
+                overvoltage: over.actual,
+            });
+        }
+        if let Some(over) = overvoltage
+            && over.actual < self.max_bus_voltage
+        {
+            return Err(VoltageProtectionError::OvervoltageBelowBusVoltage {
+                overvoltage: over.actual,
+                bus: self.max_bus_voltage,
+            });
+        }
+
+        // The overvoltage threshold is at least the bus voltage at this point, and the bus
+        // voltage scale must cover it so it can be measured.
+        let bus_volt =
+            MaxBusVoltage::covering(overvoltage.map_or(self.max_bus_voltage, |over| over.actual))?;
+
+        let mut new_gd_config1 = *gd_config1;
+        let ovp = new_gd_config1.set_ovp_level_volts(self.hardware_ovp)?;
+        if let Some(ovp) = ovp {
+            let max = overvoltage.map_or(self.max_bus_voltage, |over| over.actual);
+            if ovp.actual < max {
+                return Err(VoltageProtectionError::OvpBelowMaximumVoltage {
+                    ovp: ovp.actual,
+                    max,
+                });
+            }
+        }
+
+        fault_config2
+            .set_min_vm_motor(undervoltage.map_or(MinimumBusVoltage::NoLimit, |q| q.value));
+        fault_config2.set_max_vm_motor(overvoltage.map_or(MaximumBusVoltage::NoLimit, |q| q.value));
+        if let Some(limit) = self.undervoltage {
+            fault_config2.set_min_vm_mode(limit.recovery == VoltageFaultRecovery::AutoClear);
+        }
+        if let Some(limit) = self.overvoltage {
+            fault_config2.set_max_vm_mode(limit.recovery == VoltageFaultRecovery::AutoClear);
+        }
+        device_config1.set_bus_volt(bus_volt);
+        *gd_config1 = new_gd_config1;
+        Ok(())
+    }
+}
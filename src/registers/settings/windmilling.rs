@@ -0,0 +1,168 @@
+//! Behavior when the motor is already spinning at start, spread across `IsdConfig`,
+//! `RevDriveConfig` and `IntAlgo1`
+
+use super::super::algorithm_configuration::isd_config::{
+    BemfStationaryVoltageThreshold, BrakeCurrentThreshold, IsdConfig, ResyncThreshold,
+    ReverseDriveHandoffThreshold, ReverseDriveOpenLoopCurrent, TimeFormatA,
+};
+use super::super::algorithm_configuration::rev_drive_config::RevDriveConfig;
+use super::super::internal_algorithm_configuration::int_algo1::{
+    BrakeCurrentPersist, IntAlgo1, IsdTimeout, PersistanceTime, ReverseOpenLoopDeceleration,
+};
+use super::*;
+
+/// How initial speed detection (ISD) decides the motor state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsdDetection {
+    /// BEMF threshold to detect if motor is stationary (`IsdConfig::stat_detect_thr`)
+    pub stationary_threshold: BemfStationaryVoltageThreshold,
+    /// Fast speed detection (`IntAlgo1::fast_isd_en`)
+    pub fast: bool,
+    /// Persistence time for declaring motor has stopped (`IntAlgo1::isd_stop_time`)
+    pub stop_time: PersistanceTime,
+    /// Persistence time for declaring motor is running (`IntAlgo1::isd_run_time`)
+    pub run_time: PersistanceTime,
+    /// Timeout if speed or direction cannot be detected (`IntAlgo1::isd_timeout`)
+    pub timeout: IsdTimeout,
+}
+
+/// Brake applied to stop a spinning motor before starting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsdBrake {
+    /// Turn on all low-side FETs instead of all high-side FETs (`IsdConfig::brk_mode`)
+    pub low_side: bool,
+    /// Brake time (`IsdConfig::brk_time`)
+    pub time: TimeFormatA,
+    /// Exit the brake early once the current stays below this threshold
+    /// (`IsdConfig::brk_curr_thr`), or `None` to always brake for `time`
+    pub current_threshold: Option<BrakeCurrentThreshold>,
+    /// Persistence time for the current threshold (`IntAlgo1::brake_current_persist`)
+    pub current_persist: BrakeCurrentPersist,
+}
+
+/// Reverse drive used to decelerate a motor spinning backwards and start it forwards.
+///
+/// The open loop current and acceleration only apply with `MotorStartup1::rev_drv_config` set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReverseDrive {
+    /// Speed threshold to transition to open loop (`IsdConfig::rev_drv_handoff_thr`)
+    pub handoff_threshold: ReverseDriveHandoffThreshold,
+    /// Open loop current limit (`IsdConfig::rev_drv_open_loop_current`)
+    pub open_loop_current: ReverseDriveOpenLoopCurrent,
+    /// Open loop acceleration coefficient A1 (`RevDriveConfig::rev_drv_open_loop_accel_a1`)
+    pub accel_a1: OpenLoopAccelerationA1,
+    /// Open loop acceleration coefficient A2 (`RevDriveConfig::rev_drv_open_loop_accel_a2`)
+    pub accel_a2: OpenLoopAccelerationA2,
+    /// Share of open loop acceleration applied while decelerating
+    /// (`IntAlgo1::rev_drv_open_loop_dec`)
+    pub deceleration: ReverseOpenLoopDeceleration,
+}
+
+/// Windmilling policy.
+///
+/// Each option has its own enable bit. When ISD finds the motor spinning, the device works
+/// through them in order: resync if spinning forward above the resync threshold, reverse drive
+/// if spinning backwards, otherwise brake and then coast, whichever are enabled, before running
+/// the startup sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindmillingPolicy {
+    /// Run initial speed detection at start (`IsdConfig::isd_en`). The options below are
+    /// ignored without it.
+    pub isd: bool,
+    /// Detection parameters
+    pub detection: IsdDetection,
+    /// Resynchronize to closed loop when spinning forward above the threshold
+    pub resync: Option<ResyncThreshold>,
+    /// Drive through zero speed when spinning backwards, then continue forward
+    pub reverse_drive: Option<ReverseDrive>,
+    /// Brake before running the startup sequence
+    pub brake: Option<IsdBrake>,
+    /// Coast (Hi-Z) for the given time before running the startup sequence
+    pub coast: Option<TimeFormatA>,
+}
+
+impl WindmillingPolicy {
+    /// Reads the policy out of the ISD, reverse drive and internal algorithm registers.
+    ///
+    /// Parameters of disabled options are not kept.
+    pub fn from_registers(
+        isd_config: &IsdConfig,
+        rev_drive_config: &RevDriveConfig,
+        int_algo1: &IntAlgo1,
+    ) -> Self {
+        WindmillingPolicy {
+            isd: isd_config.isd_en(),
+            detection: IsdDetection {
+                stationary_threshold: isd_config.stat_detect_thr(),
+                fast: int_algo1.fast_isd_en(),
+                stop_time: int_algo1.isd_stop_time(),
+                run_time: int_algo1.isd_run_time(),
+                timeout: int_algo1.isd_timeout(),
+            },
+            resync: isd_config
+                .resync_en()
+                .then(|| isd_config.fw_drv_resyn_thr()),
+            reverse_drive: isd_config.rvs_dr_en().then(|| ReverseDrive {
+                handoff_threshold: isd_config.rev_drv_handoff_thr(),
+                open_loop_current: isd_config.rev_drv_open_loop_current(),
+                accel_a1: rev_drive_config.rev_drv_open_loop_accel_a1(),
+                accel_a2: rev_drive_config.rev_drv_open_loop_accel_a2(),
+                deceleration: int_algo1.rev_drv_open_loop_dec(),
+            }),
+            brake: isd_config.brake_en().then(|| IsdBrake {
+                low_side: isd_config.brk_mode(),
+                time: isd_config.brk_time(),
+                current_threshold: isd_config.brk_config().then(|| isd_config.brk_curr_thr()),
+                current_persist: int_algo1.brake_current_persist(),
+            }),
+            coast: isd_config.hiz_en().then(|| isd_config.hiz_time()),
+        }
+    }
+
+    /// Writes the policy into the ISD, reverse drive and internal algorithm registers.
+    ///
+    /// Every enable is written. Parameters of disabled options keep their value.
+    pub fn apply(
+        &self,
+        isd_config: &mut IsdConfig,
+        rev_drive_config: &mut RevDriveConfig,
+        int_algo1: &mut IntAlgo1,
+    ) {
+        isd_config.set_isd_en(self.isd);
+        isd_config.set_stat_detect_thr(self.detection.stationary_threshold);
+        int_algo1.set_fast_isd_en(self.detection.fast);
+        int_algo1.set_isd_stop_time(self.detection.stop_time);
+        int_algo1.set_isd_run_time(self.detection.run_time);
+        int_algo1.set_isd_timeout(self.detection.timeout);
+
+        isd_config.set_resync_en(self.resync.is_some());
+        if let Some(threshold) = self.resync {
+            isd_config.set_fw_drv_resyn_thr(threshold);
+        }
+
+        isd_config.set_rvs_dr_en(self.reverse_drive.is_some());
+        if let Some(reverse) = self.reverse_drive {
+            isd_config.set_rev_drv_handoff_thr(reverse.handoff_threshold);
+            isd_config.set_rev_drv_open_loop_current(reverse.open_loop_current);
+            rev_drive_config.set_rev_drv_open_loop_accel_a1(reverse.accel_a1);
+            rev_drive_config.set_rev_drv_open_loop_accel_a2(reverse.accel_a2);
+            int_algo1.set_rev_drv_open_loop_dec(reverse.deceleration);
+        }
+
+        isd_config.set_brake_en(self.brake.is_some());
+        if let Some(brake) = self.brake {
+            isd_config.set_brk_mode(brake.low_side);
+            isd_config.set_brk_time(brake.time);
+            isd_config.set_brk_config(brake.current_threshold.is_some());
+            if let Some(threshold) = brake.current_threshold {
+                isd_config.set_brk_curr_thr(threshold);
+            }
+            int_algo1.set_brake_current_persist(brake.current_persist);
+        }
+
+        isd_config.set_hiz_en(self.coast.is_some());
+        if let Some(time) = self.coast {
+            isd_config.set_hiz_time(time);
+        }
+    }
+}